@@ -15,6 +15,7 @@ pub enum RevocationReason {
     RemoveFromCRL,
     PrivilegeWithdrawn,
     AACompromise,
+    Expired,
     Other(String),
 }
 
@@ -1,11 +1,31 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, String, Vec,
+    contract, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, IntoVal, String,
+    Symbol, Vec, token, xdr::ToXdr,
 };
 
 const MAX_BATCH_SIZE: u32 = 50;
 const BASE_VERIFICATION_COST: u64 = 10;
 const COST_PER_CERTIFICATE: u64 = 5;
+const MAX_CERTIFICATE_ID_LENGTH: u32 = 64;
+const MAX_MEMO_LENGTH: u32 = 256;
+const MAX_TRANSFER_DATA_LENGTH: u32 = 512;
+const MAX_ALIAS_LENGTH: u32 = 64;
+const MAX_BACKUP_URIS: u32 = 5;
+const MAX_TAGS_PER_CERTIFICATE: u32 = 10;
+const MAX_TAG_LENGTH: u32 = 32;
+const BPS_DENOMINATOR: u32 = 10_000;
+const CONTRACT_VERSION: (u32, u32, u32) = (0, 1, 0);
+const DISPUTE_WINDOW_SECONDS: u64 = 30 * 24 * 60 * 60;
+const CLAIM_SIGNATURE_VALIDITY_SECONDS: u64 = 7 * 24 * 60 * 60;
+const MAX_CLAIM_PAYLOAD_LENGTH: u32 = 1024;
+
+// Bits of `Certificate::flags`
+pub const FLAG_REVOKED: u32 = 1 << 0;
+pub const FLAG_FROZEN: u32 = 1 << 1;
+pub const FLAG_SUSPENDED: u32 = 1 << 2;
+pub const FLAG_RENOUNCED: u32 = 1 << 3;
+pub const FLAG_AUTO_REVOKE_ON_EXPIRY: u32 = 1 << 4;
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec, symbol_short};
 
 // Soroban event emission - topics must be a tuple of up to 4 elements
@@ -129,6 +149,95 @@ pub use multisig::{
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Certificate {
+    pub id: String,
+    pub issuer: Address,
+    pub owner: Address,
+    pub metadata_uri: String,
+    pub issued_at: u64,
+    pub flags: u32,                            // FLAG_* bits; read through the accessor methods
+    pub revocation_reason: Option<String>,
+    pub revoked_at: Option<u64>,
+    pub revoked_by: Option<Address>,
+    // Upgrade-related fields
+    pub version: CertificateVersion,           // Current version
+    pub parent_certificate_id: Option<String>, // Points to previous version
+    pub child_certificate_id: Option<String>,  // Points to next version
+    pub is_upgradable: bool,                   // Whether this certificate can be upgraded
+    pub upgrade_rules: Vec<UpgradeRule>,       // Valid upgrade paths
+    pub compatibility_matrix: CompatibilityMatrix, // Version compatibility info
+    // Freeze-related fields
+    pub freeze_info: Option<FrozenCertificateInfo>, // Freeze details
+    // Expiry-related fields
+    pub expires_at: Option<u64>,               // Timestamp after which the certificate is expired
+    // Suspension-related fields
+    pub suspension_reason: Option<String>,     // Reason for the current suspension
+    // Concurrency control
+    pub nonce: u32,                            // Incremented on every mutation
+    // Integrity
+    pub metadata_hash: Option<BytesN<32>>,     // SHA-256 of the off-chain metadata content
+    // Auditing
+    pub last_modified_at: u64,                 // Timestamp of the most recent mutation
+    // Scheduled revocation
+    pub scheduled_revocation_at: Option<u64>,  // Timestamp from which the certificate reads as revoked
+    pub scheduled_revocation_reason: Option<String>, // Reason recorded with the scheduled revocation
+    // Reissuance lineage
+    pub supersedes: Option<String>,            // Certificate this one replaced on reissue
+    pub superseded_by: Option<String>,         // Certificate that replaced this one on reissue
+    // Metadata mirrors
+    pub backup_uris: Vec<String>,              // Fallback locations of the metadata, in preference order
+    // Transfer limits
+    pub max_transfers: Option<u32>,            // Completed transfers allowed before auto-revocation
+    pub transfer_count: u32,                   // Completed transfers so far
+    // External verification
+    pub verifier: Option<Address>,             // Registered verifier contract the holder points to
+}
+
+impl Certificate {
+    /// Whether any of the given `FLAG_*` bits is set
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Set or clear the given `FLAG_*` bits
+    pub fn set_flag(&mut self, flag: u32, on: bool) {
+        if on {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    /// Whether the certificate has been revoked
+    pub fn is_revoked(&self) -> bool {
+        self.has_flag(FLAG_REVOKED)
+    }
+
+    /// Whether the certificate is frozen
+    pub fn is_frozen(&self) -> bool {
+        self.has_flag(FLAG_FROZEN)
+    }
+
+    /// Whether the certificate is temporarily suspended
+    pub fn is_suspended(&self) -> bool {
+        self.has_flag(FLAG_SUSPENDED)
+    }
+
+    /// Whether the owner has disclaimed the certificate
+    pub fn is_renounced(&self) -> bool {
+        self.has_flag(FLAG_RENOUNCED)
+    }
+
+    /// Whether expiry is reported as revocation
+    pub fn auto_revoke_on_expiry(&self) -> bool {
+        self.has_flag(FLAG_AUTO_REVOKE_ON_EXPIRY)
+    }
+}
+
+/// A certificate with its flags unpacked into booleans, as returned by
+/// `get_certificate` for clients that predate the packed `flags` field
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateView {
     pub id: String,
     pub issuer: Address,
     pub owner: Address,
@@ -148,6 +257,98 @@ pub struct Certificate {
     // Freeze-related fields
     pub frozen: bool,                          // Whether the certificate is frozen
     pub freeze_info: Option<FrozenCertificateInfo>, // Freeze details
+    // Renounce-related fields
+    pub renounced: bool,                       // Whether the owner has disclaimed the certificate
+    // Expiry-related fields
+    pub expires_at: Option<u64>,               // Timestamp after which the certificate is expired
+    pub auto_revoke_on_expiry: bool,           // Whether expiry is reported as revocation
+    // Suspension-related fields
+    pub suspended: bool,                       // Whether the certificate is temporarily suspended
+    pub suspension_reason: Option<String>,     // Reason for the current suspension
+    // Concurrency control
+    pub nonce: u32,                            // Incremented on every mutation
+    // Integrity
+    pub metadata_hash: Option<BytesN<32>>,     // SHA-256 of the off-chain metadata content
+    // Auditing
+    pub last_modified_at: u64,                 // Timestamp of the most recent mutation
+    // Scheduled revocation
+    pub scheduled_revocation_at: Option<u64>,  // Timestamp from which the certificate reads as revoked
+    pub scheduled_revocation_reason: Option<String>, // Reason recorded with the scheduled revocation
+    // Reissuance lineage
+    pub supersedes: Option<String>,            // Certificate this one replaced on reissue
+    pub superseded_by: Option<String>,         // Certificate that replaced this one on reissue
+    // Metadata mirrors
+    pub backup_uris: Vec<String>,              // Fallback locations of the metadata, in preference order
+    // Transfer limits
+    pub max_transfers: Option<u32>,            // Completed transfers allowed before auto-revocation
+    pub transfer_count: u32,                   // Completed transfers so far
+    // External verification
+    pub verifier: Option<Address>,             // Registered verifier contract the holder points to
+}
+
+impl From<Certificate> for CertificateView {
+    fn from(cert: Certificate) -> Self {
+        let revoked = cert.is_revoked();
+        let frozen = cert.is_frozen();
+        let renounced = cert.is_renounced();
+        let auto_revoke_on_expiry = cert.auto_revoke_on_expiry();
+        let suspended = cert.is_suspended();
+        CertificateView {
+            id: cert.id,
+            issuer: cert.issuer,
+            owner: cert.owner,
+            metadata_uri: cert.metadata_uri,
+            issued_at: cert.issued_at,
+            revoked,
+            revocation_reason: cert.revocation_reason,
+            revoked_at: cert.revoked_at,
+            revoked_by: cert.revoked_by,
+            version: cert.version,
+            parent_certificate_id: cert.parent_certificate_id,
+            child_certificate_id: cert.child_certificate_id,
+            is_upgradable: cert.is_upgradable,
+            upgrade_rules: cert.upgrade_rules,
+            compatibility_matrix: cert.compatibility_matrix,
+            frozen,
+            freeze_info: cert.freeze_info,
+            renounced,
+            expires_at: cert.expires_at,
+            auto_revoke_on_expiry,
+            suspended,
+            suspension_reason: cert.suspension_reason,
+            nonce: cert.nonce,
+            metadata_hash: cert.metadata_hash,
+            last_modified_at: cert.last_modified_at,
+            scheduled_revocation_at: cert.scheduled_revocation_at,
+            scheduled_revocation_reason: cert.scheduled_revocation_reason,
+            supersedes: cert.supersedes,
+            superseded_by: cert.superseded_by,
+            backup_uris: cert.backup_uris,
+            max_transfers: cert.max_transfers,
+            transfer_count: cert.transfer_count,
+            verifier: cert.verifier,
+        }
+    }
+}
+
+/// Certificate status as seen by verification queries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CertificateStatus {
+    Valid,    // Certificate is active
+    Revoked,   // Certificate was revoked (or expired with auto-revoke)
+    Expired,   // Certificate passed its expiry time
+    Suspended, // Certificate is on a temporary hold
+}
+
+/// Result of verifying a single certificate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateVerification {
+    pub id: String,
+    pub status: CertificateStatus,
+    pub revocation_reason: Option<RevocationReason>,
+    pub verified_at: u64,
 }
 
 /// Transfer status enum
@@ -161,6 +362,16 @@ pub enum TransferStatus {
     Completed,    // Transfer completed successfully
 }
 
+/// Who may complete an accepted transfer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompleterPolicy {
+    AnyParty,      // Sender, recipient or issuer
+    RecipientOnly, // Only the recipient
+    SenderOnly,    // Only the sender
+    IssuerOnly,    // Only the certificate issuer
+}
+
 /// Transfer request structure
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -174,8 +385,97 @@ pub struct TransferRequest {
     pub completed_at: Option<u64>, // When transfer was completed
     pub status: TransferStatus,   // Current status
     pub require_revocation: bool, // Whether to revoke on transfer
-    pub transfer_fee: u64,        // Transfer fee (0 for no fee)
+    pub transfer_fee: i128,       // Transfer fee in token units (0 for no fee)
     pub memo: Option<String>,     // Optional memo for transfer
+    pub notify_recipient: bool,   // Whether to call the recipient contract's receive hook
+    pub complete_by: Option<u64>, // Deadline for completing the transfer
+    pub completer_policy: CompleterPolicy, // Who may complete the transfer
+    pub expires_at: Option<u64>,  // Last time the transfer can be accepted
+    pub data: Option<Bytes>,      // Optional structured payload, e.g. an invoice reference
+    pub prepaid_token: Option<Address>, // Token the sender escrowed the fee in, if prepaid
+}
+
+/// Optional terms fixed when a transfer is initiated. They cannot be changed
+/// afterwards, so the recipient accepts exactly what it was offered.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferOptions {
+    pub complete_by: Option<u64>, // Deadline for completing the transfer once accepted
+    pub ttl_seconds: u64,         // Seconds the transfer may wait for acceptance (0 = admin default)
+    pub data: Option<Bytes>,      // Structured payload, e.g. an invoice reference
+    pub prepay_fee: bool,         // Escrow the fee from the sender now instead of charging the recipient
+}
+
+/// Transfer offer open to the first eligible claimer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenOffer {
+    pub id: String,               // Offer ID; becomes the transfer ID once claimed
+    pub certificate_id: String,   // Certificate on offer
+    pub from_address: Address,    // Owner making the offer
+    pub transfer_fee: i128,       // Fee the claimer pays in token units
+    pub memo: Option<String>,     // Carried into the transfer and its history on claim
+    pub created_at: u64,
+    pub expires_at: u64,          // Last time the offer can be claimed
+    pub status: TransferStatus,   // Pending until claimed, then Completed or Cancelled
+    pub claimed_by: Option<Address>,
+}
+
+/// Fee settlement configuration. When set, `transfer_fee` is paid in `token`
+/// by the recipient on completion and split between issuer and treasury.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub token: Address,     // Token the fee is paid in
+    pub treasury: Address,  // Receives the platform share
+    pub royalty_bps: u32,   // Issuer's share in basis points (max 10000)
+}
+
+/// Admin-set parameters, gathered for integrators in a single read
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractConfig {
+    pub admin: Option<Address>,
+    pub pending_admin: Option<Address>,
+    pub fee_config: Option<FeeConfig>,  // Fee token, treasury and royalty split
+    pub min_transfer_fee: i128,
+    pub max_transfer_fee: i128,         // 0 = no cap
+    pub require_memo_on_fee: bool,
+    pub allow_self_transfer: bool,
+    pub max_certs_per_owner: u32,       // 0 = unlimited
+    pub max_transfers_per_window: u32,  // 0 = unlimited
+    pub rate_window_seconds: u64,
+    pub issuer_auto_transfer: bool,
+    pub require_owner_consent_on_issue: bool,
+    pub revocation_hook: Option<Address>,
+    pub strict_revocation_hook: bool,
+    pub emit_events: bool,
+    pub default_transfer_ttl: u64,      // 0 = pending transfers never expire
+    pub min_transfer_age_seconds: u64,
+    pub certificate_id_prefix: String,  // Empty = any prefix
+    pub max_certificate_id_length: u32, // 0 = MAX_CERTIFICATE_ID_LENGTH
+}
+
+/// Headline counters for dashboards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractStats {
+    pub total_certificates: u32,  // Certificates issued
+    pub total_revoked: u32,       // Certificates revoked
+    pub total_transfers: u64,     // Transfers initiated
+    pub pending_transfers: u32,   // Transfers awaiting acceptance
+}
+
+/// Number of transfer requests in each status. Purged requests keep
+/// counting under the status they were purged in.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StatusCounts {
+    pub pending: u32,
+    pub accepted: u32,
+    pub completed: u32,
+    pub rejected: u32,
+    pub cancelled: u32,
 }
 
 /// Transfer history entry
@@ -187,8 +487,29 @@ pub struct TransferHistory {
     pub from_address: Address,
     pub to_address: Address,
     pub transferred_at: u64,
-    pub transfer_fee: u64,
+    pub transfer_fee: i128,
     pub memo: Option<String>,
+    pub data: Option<Bytes>,
+}
+
+/// Certificate issued event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CertificateIssuedEvent {
+    pub certificate_id: String,
+    pub issuer: Address,
+    pub owner: Address,
+    pub issued_at: u64,
+}
+
+/// Certificate revoked event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CertificateRevokedEvent {
+    pub certificate_id: String,
+    pub revoked_by: Address,
+    pub revoked_at: u64,
+    pub reason: String,
 }
 
 /// Events for certificate transfers
@@ -200,7 +521,7 @@ pub struct TransferInitiatedEvent {
     pub from_address: Address,
     pub to_address: Address,
     pub initiated_at: u64,
-    pub transfer_fee: u64,
+    pub transfer_fee: i128,
 }
 
 #[contracttype]
@@ -218,7 +539,7 @@ pub struct TransferCompletedEvent {
     pub from_address: Address,
     pub to_address: Address,
     pub completed_at: u64,
-    pub transfer_fee: u64,
+    pub transfer_fee: i128,
 }
 
 #[contracttype]
@@ -235,6 +556,16 @@ pub struct TransferCancelledEvent {
     pub cancelled_at: u64,
 }
 
+/// Emitted when a pending transfer is cleaned up after its acceptance
+/// deadline, as opposed to being cancelled by a party
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TransferExpiredEvent {
+    pub transfer_id: String,
+    pub certificate_id: String,
+    pub expired_at: u64,
+}
+
 /// Upgrade events
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -287,6 +618,123 @@ pub struct CertificateArchivedEvent {
     pub reason: String,
 }
 
+/// Certificate renounced event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CertificateRenouncedEvent {
+    pub certificate_id: String,
+    pub owner: Address,
+    pub renounced_at: u64,
+}
+
+/// Issuer control transferred event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct IssuerTransferredEvent {
+    pub certificate_id: String,
+    pub old_issuer: Address,
+    pub new_issuer: Address,
+    pub transferred_at: u64,
+}
+
+/// Certificate suspended event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CertificateSuspendedEvent {
+    pub certificate_id: String,
+    pub suspended_by: Address,
+    pub suspended_at: u64,
+    pub reason: String,
+}
+
+/// Certificate reinstated from suspension event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CertificateReinstatedEvent {
+    pub certificate_id: String,
+    pub reinstated_by: Address,
+    pub reinstated_at: u64,
+}
+
+/// Expired certificate reclaimed by its issuer event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CertificateReclaimedEvent {
+    pub certificate_id: String,
+    pub previous_owner: Address,
+    pub issuer: Address,
+    pub reclaimed_at: u64,
+}
+
+/// What caused a certificate's ownership to change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OwnershipChangeCause {
+    Transfer,       // Completion of a two-step transfer
+    DirectTransfer, // Single-step transfer by the owner
+    Reclaim,        // Issuer reclaimed an expired certificate
+    Renounce,       // Owner disclaimed the certificate
+}
+
+/// Event emitted from every path that changes who owns a certificate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnershipChangedEvent {
+    pub id: String,
+    pub old_owner: Address,
+    pub new_owner: Option<Address>, // None when the certificate was renounced
+    pub at: u64,
+    pub cause: OwnershipChangeCause,
+}
+
+/// Record of an owner appealing a revocation during its grace window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevocationAppeal {
+    pub appealed_by: Address,
+    pub appealed_at: u64,
+    pub reason: Option<String>, // Reason of the revocation that was appealed
+}
+
+/// Owner's contest of a revocation, and its outcome once resolved
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevocationDispute {
+    pub disputed_by: Address,
+    pub statement: String,
+    pub disputed_at: u64,
+    pub resolved_by: Option<Address>,
+    pub resolved_at: Option<u64>,
+    pub upheld: Option<bool>, // Some(true) keeps the revocation, Some(false) reinstated
+}
+
+/// Co-issuer weights for revoking a certificate. Revocation happens once the
+/// approving co-issuers' weights add up to `weight_threshold`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeightedRevocationConfig {
+    pub weights: Vec<(Address, u32)>,
+    pub weight_threshold: u32,
+}
+
+/// Approvers who must sign off before an org-owned certificate's transfer
+/// can complete
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferApprovalConfig {
+    pub approvers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Event emitted when administration is handed over
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangedEvent {
+    pub old_admin: Address,
+    pub new_admin: Address,
+    pub changed_at: u64,
+}
+
 /// Freeze information for a certificate
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -356,6 +804,8 @@ pub enum CertificateError {
     TransferNotFound,
     TransferNotPending,
     TransferNotAuthorized,
+    SelfTransferNotAllowed,
+    TransferAlreadyActive,
     InsufficientBalance,
     InvalidTransferStatus,
     // Upgrade errors
@@ -375,17 +825,49 @@ pub enum CertificateError {
     FreezeDurationExceeded,
     FreezeDurationInvalid,
     FreezeNotExpired,
+    // Renounce errors
+    AlreadyRenounced,
+    CertificateFrozen,
+    OwnerLimitReached,
+    // Suspension errors
+    AlreadySuspended,
+    NotSuspended,
+    CertificateSuspended,
+    VersionMismatch,
+    RecipientRejected,
+    InvalidSignature,
+    TransferDeadlinePassed,
+    RecipientDenied,
+    RateLimited,
+    RecipientNotWhitelisted,
+    CounterOverflow,
+    TransferExpired,
+    Reentrancy,
+    NotRevoked,
+    DisputeWindowClosed,
+    CertificateTooNew,
+    InsufficientApprovals,
+    RevocationHookFailed,
 }
 
 /// Storage keys for the contract
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
+    Admin,                    // Contract administrator
+    PendingAdmin,             // Proposed administrator awaiting acceptance
     Certificate(String),      // Certificate ID -> Certificate
-    TransferRequest(String),  // Transfer ID -> TransferRequest
-    TransferHistory(String),  // Certificate ID -> Vec<TransferHistory>
-    PendingTransfers(Address), // Address -> Vec<TransferID> (transfers pending acceptance)
-    TransferCount,            // Total number of transfers
+    IssuerPublicKey(Address), // Issuer -> Ed25519 public key used for off-chain issuance
+    Prerequisites(String),    // Certificate ID -> Vec<String> of prerequisite certificate IDs
+    Denylisted(Address),      // Address -> bool, blocked from receiving certificates
+    IssuerUriPrefix(Address), // Issuer -> String prefix every metadata URI must start with
+    Lock,                     // bool, held while a function is making external calls
+    OwnerAlias(Address),      // Owner -> display alias; informational only
+    Verifiers,                // Vec<(Address, String)> of trusted verifier contracts and their names
+    Transfer(TransferKey),    // Transfer requests, history and per-transfer state
+    Config(ConfigKey),        // Admin-set parameters
+    Index(IndexKey),          // Lookup indexes and supply counters
+    Revocation(RevocationKey), // Delegated, scheduled, disputed and co-signed revocation state
     // Upgrade-related storage
     UpgradeRequest(String),   // Upgrade ID -> UpgradeRequest
     UpgradeHistory(String),   // Certificate ID -> Vec<UpgradeRequest>
@@ -400,6 +882,80 @@ pub enum DataKey {
     FreezeHistory(String),    // Certificate ID -> Vec<FreezeEvent>
 }
 
+/// Storage keys for transfer state, nested under `DataKey::Transfer`
+#[contracttype]
+#[derive(Clone)]
+pub enum TransferKey {
+    TransferRequest(String),  // Transfer ID -> TransferRequest
+    TransferHistory(String),  // Certificate ID -> Vec<TransferHistory>
+    PendingTransfers(Address), // Address -> Vec<TransferID> (transfers pending acceptance)
+    TransferCount,            // Total number of transfers
+    PendingTransferTotal,     // Number of transfers awaiting acceptance across all recipients
+    StatusCounts,             // StatusCounts of transfer requests per status
+    AddressHistory(Address),  // Address -> Vec<TransferHistory> (completed transfers sent or received)
+    ActiveTransfer(String),   // Certificate ID -> Transfer ID (pending or accepted transfer)
+    TransferRate(Address),    // Owner -> Vec<u64> of initiation times within the current window
+    Preapproval(Address, Address), // (Recipient, Sender) -> bool, incoming transfers accepted on initiation
+    TransferWhitelist(String), // Certificate ID -> Vec<Address> of permitted recipients
+    CertTransfers(String),    // Certificate ID -> Vec<TransferID> of every transfer ever initiated
+    TransferApprovers(String), // Certificate ID -> TransferApprovalConfig for org-owned certificates
+    TransferApprovals(String), // Transfer ID -> Vec<Address> of approvers who signed off
+    UsedTransferId(String),   // Transfer ID -> bool, set on first use and kept after purging
+    OpenOffer(String),        // Offer ID -> OpenOffer
+}
+
+/// Storage keys for admin-set parameters, nested under `DataKey::Config`
+#[contracttype]
+#[derive(Clone)]
+pub enum ConfigKey {
+    MaxCertsPerOwner,         // Maximum active certificates per owner (0 = unlimited)
+    AllowSelfTransfer,        // Whether transfers with from == to are permitted
+    FeeConfig,                // FeeConfig for moving transfer fees
+    MinTransferFee,           // Lowest transfer fee accepted at initiation
+    MaxTransferFee,           // Highest transfer fee accepted at initiation (0 = no cap)
+    RequireMemoOnFee,         // Whether fee-bearing transfers must carry a memo
+    IssuerAutoTransfer,       // Whether issuers may transfer without recipient acceptance
+    RequireOwnerConsent,      // Whether issuance also needs the owner's authorization
+    RevocationHook,           // Contract notified via `on_certificate_revoked(id)` on revocation
+    StrictRevocationHook,     // Whether a failing revocation hook reverts the revocation
+    EmitEvents,               // Whether transfer lifecycle events are published (default true)
+    DefaultTransferTtl,       // Seconds a transfer may stay pending when no TTL is set (0 = no expiry)
+    MinAgeBeforeTransfer,     // Seconds after issuance before a certificate may be transferred
+    MaxTransfersPerWindow,    // Transfers an owner may initiate per rate window (0 = unlimited)
+    RateWindowSeconds,        // Length of the sliding rate window in seconds
+    CertificateIdPrefix,      // String prefix every new certificate ID must start with
+    MaxCertificateIdLength,   // Longest ID accepted for new certificates (0 = MAX_CERTIFICATE_ID_LENGTH)
+}
+
+/// Storage keys for lookup indexes and counters, nested under `DataKey::Index`
+#[contracttype]
+#[derive(Clone)]
+pub enum IndexKey {
+    RevokedIndex,             // Vec<String> of revoked certificate IDs, in revocation order
+    OwnerCertificates(Address), // Address -> Vec<String> (certificate IDs currently owned)
+    IssuanceLog,              // Vec<(String, u64)> of certificate ID and issuance time, in issuance order
+    ExpiryIndex,              // Vec<(u64, String)> of expiry time and certificate ID, sorted by expiry
+    IssuerCertificates(Address), // Issuer -> Vec<String> (certificate IDs under the issuer's control)
+    HashToId(BytesN<32>),     // Metadata content hash -> Certificate ID
+    NextCertificateIndex,     // Monotonic count of certificates ever created; never reset
+    CertificateTags(String),  // Certificate ID -> Vec<String> of tags
+    TagIndex(String),         // Tag -> Vec<String> of tagged certificate IDs
+    ActiveCount,              // u64 count of issued certificates that are not revoked
+    RevokedCount,             // u64 count of revoked certificates
+}
+
+/// Storage keys for revocation workflows, nested under `DataKey::Revocation`
+#[contracttype]
+#[derive(Clone)]
+pub enum RevocationKey {
+    RevocationDelegates(Address), // Issuer -> Vec<Address> allowed to revoke on the issuer's behalf
+    RevocationGrace(String),  // Certificate ID -> bool, scheduled revocation the owner may appeal
+    RevocationAppeal(String), // Certificate ID -> RevocationAppeal
+    Dispute(String),          // Certificate ID -> RevocationDispute
+    RevocationWeights(String), // Certificate ID -> WeightedRevocationConfig
+    RevocationApprovals(String), // Certificate ID -> Vec<Address> of co-issuers approving revocation
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SingleVerificationResult {
@@ -592,30 +1148,50 @@ impl CertificateContract {
         
         Ok(())
     }
-}
 
-#[contractimpl]
-impl CertificateContract {
-    pub fn issue_certificate(
-        env: Env,
+    /// Validate and store a freshly issued certificate. Every issuance path
+    /// goes through here, so owner consent is enforced in one place.
+    fn store_new_certificate(
+        env: &Env,
         id: String,
         issuer: Address,
         owner: Address,
         metadata_uri: String,
-    ) {
-        issuer.require_auth();
+        metadata_hash: Option<BytesN<32>>,
+        expires_at: Option<u64>,
+        auto_revoke_on_expiry: bool,
+        backup_uris: Vec<String>,
+    ) -> Result<(), CertificateError> {
+        // Reject ids and URIs that cannot be looked up or verified
+        if id.len() == 0 || id.len() > MAX_CERTIFICATE_ID_LENGTH || metadata_uri.len() == 0 {
+            return Err(CertificateError::InvalidData);
+        }
+
+        Self::ensure_id_format(env, &id)?;
+        Self::require_owner_consent(env, &issuer, &owner);
 
         if env.storage().instance().has(&id) {
-            panic!("Certificate already exists");
+            return Err(CertificateError::AlreadyExists);
+        }
+
+        // Content hashes must resolve to a single certificate
+        if let Some(ref hash) = metadata_hash {
+            if env.storage().instance().has(&DataKey::Index(IndexKey::HashToId(hash.clone()))) {
+                return Err(CertificateError::AlreadyExists);
+            }
         }
 
+        Self::ensure_uri_allowed(env, &issuer, &metadata_uri)?;
+        Self::ensure_not_denied(env, &owner)?;
+        Self::ensure_owner_capacity(env, &owner)?;
+
         let cert = Certificate {
             id: id.clone(),
             issuer,
             owner,
             metadata_uri,
             issued_at: env.ledger().timestamp(),
-            revoked: false,
+            flags: if auto_revoke_on_expiry { FLAG_AUTO_REVOKE_ON_EXPIRY } else { 0 },
             revocation_reason: None,
             revoked_at: None,
             revoked_by: None,
@@ -629,7 +1205,7 @@ impl CertificateContract {
             parent_certificate_id: None,
             child_certificate_id: None,
             is_upgradable: false,
-            upgrade_rules: Vec::new(&env),
+            upgrade_rules: Vec::new(env),
             compatibility_matrix: CompatibilityMatrix {
                 version: CertificateVersion {
                     major: 1,
@@ -637,79 +1213,3008 @@ impl CertificateContract {
                     patch: 0,
                     build: None,
                 },
-                compatible_versions: Vec::new(&env),
+                compatible_versions: Vec::new(env),
                 backward_compatible: true,
                 forward_compatible: true,
             },
             // Initialize freeze fields
-            frozen: false,
             freeze_info: None,
+            expires_at,
+            suspension_reason: None,
+            nonce: 0,
+            metadata_hash,
+            last_modified_at: env.ledger().timestamp(),
+            scheduled_revocation_at: None,
+            scheduled_revocation_reason: None,
+            supersedes: None,
+            superseded_by: None,
+            backup_uris,
+            max_transfers: None,
+            transfer_count: 0,
+            verifier: None,
         };
 
+        Self::advance_certificate_index(env)?;
         env.storage().instance().set(&id, &cert);
-    }
+        if let Some(ref hash) = cert.metadata_hash {
+            env.storage().instance().set(&DataKey::Index(IndexKey::HashToId(hash.clone())), &id);
+        }
+        Self::add_to_owner_index(env, &cert.owner, &id);
+        Self::add_to_issuer_index(env, &cert.issuer, &id);
+        if let Some(expires_at) = cert.expires_at {
+            Self::add_to_expiry_index(env, &id, expires_at);
+        }
 
-    pub fn revoke_certificate(env: Env, id: String, reason: String) {
-        let mut cert: Certificate = env
+        // Count before logging, so a first-time seed excludes this certificate
+        Self::adjust_supply(env, DataKey::Index(IndexKey::ActiveCount), true);
+        let mut log: Vec<(String, u64)> = env
             .storage()
             .instance()
-            .get(&id)
-            .expect("Certificate not found");
+            .get(&DataKey::Index(IndexKey::IssuanceLog))
+            .unwrap_or(Vec::new(env));
+        log.push_back((id.clone(), cert.issued_at));
+        env.storage().instance().set(&DataKey::Index(IndexKey::IssuanceLog), &log);
 
-        cert.issuer.require_auth();
+        env.events().publish(
+            (symbol_short!("cert_iss"), id.clone()),
+            CertificateIssuedEvent {
+                certificate_id: id,
+                issuer: cert.issuer,
+                owner: cert.owner,
+                issued_at: cert.issued_at,
+            },
+        );
 
-        if cert.revoked {
-            panic!("Certificate already revoked");
-        }
+        Ok(())
+    }
 
-        cert.revoked = true;
-        cert.revocation_reason = Some(reason);
-        cert.revoked_at = Some(env.ledger().timestamp());
-        cert.revoked_by = Some(cert.issuer.clone());
+    /// Read the certificate index. Deployments that predate the index start
+    /// from the number of logged issuances, so the log is only read once.
+    fn current_certificate_index(env: &Env) -> u64 {
+        if let Some(current) = env.storage().instance().get(&DataKey::Index(IndexKey::NextCertificateIndex)) {
+            return current;
+        }
+        let logged: Vec<(String, u64)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::IssuanceLog))
+            .unwrap_or(Vec::new(env));
+        logged.len() as u64
+    }
 
-        env.storage().instance().set(&id, &cert);
+    /// Advance the certificate index. The index only ever moves forward, so
+    /// it can never hand out a value that was already used.
+    fn advance_certificate_index(env: &Env) -> Result<u64, CertificateError> {
+        let current = Self::current_certificate_index(env);
+        let next = current.checked_add(1).ok_or(CertificateError::CounterOverflow)?;
+        env.storage().instance().set(&DataKey::Index(IndexKey::NextCertificateIndex), &next);
+        Ok(current)
     }
 
-    /// Freeze a certificate temporarily during a dispute
-    /// 
-    /// # Arguments
-    /// * `id` - Certificate ID to freeze
-    /// * `admin` - Admin address that has authority to freeze
-    /// * `reason` - Reason for freezing the certificate
-    /// * `duration_days` - Number of days to freeze (0 for permanent freeze, max 90 days)
-    /// 
-    /// # Returns
-    /// * `CertificateFrozenEvent` - Event emitted when certificate is frozen
-    pub fn freeze_certificate(
-        env: Env,
-        id: String,
-        admin: Address,
-        reason: String,
-        duration_days: u32,
-    ) -> CertificateFrozenEvent {
-        admin.require_auth();
+    /// Mark a certificate as revoked and add it to the revocation index.
+    /// The caller is responsible for persisting the certificate.
+    fn mark_revoked(env: &Env, cert: &mut Certificate, reason: String, revoked_by: Address) {
+        Self::apply_revocation(env, cert, reason.clone(), revoked_by.clone());
 
-        let mut cert: Certificate = env
+        // Count before indexing, so a first-time seed excludes this certificate
+        Self::adjust_supply(env, DataKey::Index(IndexKey::ActiveCount), false);
+        Self::adjust_supply(env, DataKey::Index(IndexKey::RevokedCount), true);
+        let mut index: Vec<String> = env
             .storage()
             .instance()
-            .get(&id)
-            .expect("Certificate not found");
+            .get(&DataKey::Index(IndexKey::RevokedIndex))
+            .unwrap_or(Vec::new(env));
+        index.push_back(cert.id.clone());
+        env.storage().instance().set(&DataKey::Index(IndexKey::RevokedIndex), &index);
 
-        // Check if already frozen
-        if cert.frozen {
-            panic!("Certificate is already frozen");
-        }
+        env.events().publish(
+            (symbol_short!("cert_rev"), cert.id.clone()),
+            CertificateRevokedEvent {
+                certificate_id: cert.id.clone(),
+                revoked_by,
+                revoked_at: env.ledger().timestamp(),
+                reason,
+            },
+        );
+    }
 
-        // Check if certificate is revoked
-        if cert.revoked {
-            panic!("Cannot freeze a revoked certificate");
-        }
+    /// Step an active/revoked supply counter up or down by one
+    fn adjust_supply(env: &Env, key: DataKey, increase: bool) {
+        let count = Self::supply_count(env, &key);
+        let count = if increase { count + 1 } else { count.saturating_sub(1) };
+        env.storage().instance().set(&key, &count);
+    }
 
-        // Validate duration
-        if duration_days > 90 {
-            panic!("Freeze duration cannot exceed 90 days");
+    /// Read an active/revoked supply counter for an update. Deployments that
+    /// predate the counters seed them here, on their first write, from the
+    /// issuance log and the revocation index.
+    fn supply_count(env: &Env, key: &DataKey) -> u64 {
+        if let Some(count) = env.storage().instance().get(key) {
+            return count;
         }
-
+        let issued: Vec<(String, u64)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::IssuanceLog))
+            .unwrap_or(Vec::new(env));
+        let revoked: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::RevokedIndex))
+            .unwrap_or(Vec::new(env));
+        match key {
+            DataKey::Index(IndexKey::RevokedCount) => revoked.len() as u64,
+            _ => issued.len().saturating_sub(revoked.len()) as u64,
+        }
+    }
+
+    /// Set the revocation fields on an in-memory certificate
+    fn apply_revocation(env: &Env, cert: &mut Certificate, reason: String, revoked_by: Address) {
+        cert.set_flag(FLAG_REVOKED, true);
+        cert.revocation_reason = Some(reason);
+        cert.revoked_at = Some(env.ledger().timestamp());
+        cert.revoked_by = Some(revoked_by);
+    }
+
+    /// Bump the nonce and stamp the modification time, as done on every save
+    fn touch_certificate(env: &Env, cert: &mut Certificate) {
+        cert.nonce = cert.nonce.wrapping_add(1);
+        cert.last_modified_at = env.ledger().timestamp();
+    }
+
+    /// Persist a modified certificate, bumping its nonce so stale writers can
+    /// be detected and stamping the modification time
+    fn save_certificate(env: &Env, cert: &mut Certificate) {
+        Self::touch_certificate(env, cert);
+        env.storage().instance().set(&cert.id, cert);
+    }
+
+    /// Check whether a certificate has passed its expiry time
+    fn is_expired(env: &Env, cert: &Certificate) -> bool {
+        match cert.expires_at {
+            Some(expires_at) => env.ledger().timestamp() >= expires_at,
+            None => false,
+        }
+    }
+
+    /// Check whether a scheduled revocation has taken effect
+    fn is_scheduled_revocation_due(env: &Env, cert: &Certificate) -> bool {
+        match cert.scheduled_revocation_at {
+            Some(effective_at) => env.ledger().timestamp() >= effective_at,
+            None => false,
+        }
+    }
+
+    /// Record a transfer initiation against the owner's sliding window,
+    /// failing if the owner has used up their allowance
+    fn consume_transfer_allowance(env: &Env, owner: &Address) -> Result<(), CertificateError> {
+        let max: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MaxTransfersPerWindow))
+            .unwrap_or(0);
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::RateWindowSeconds))
+            .unwrap_or(0);
+        if max == 0 || window == 0 {
+            return Ok(());
+        }
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::Transfer(TransferKey::TransferRate(owner.clone()));
+        let recent: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        // Drop initiations that have slid out of the window
+        let mut in_window = Vec::new(env);
+        for initiated_at in recent.iter() {
+            if initiated_at.saturating_add(window) > now {
+                in_window.push_back(initiated_at);
+            }
+        }
+
+        if in_window.len() >= max {
+            return Err(CertificateError::RateLimited);
+        }
+
+        in_window.push_back(now);
+        env.storage().instance().set(&key, &in_window);
+        Ok(())
+    }
+
+    /// Check whether `value` begins with `prefix`
+    fn starts_with(env: &Env, value: &String, prefix: &String) -> bool {
+        if prefix.len() > value.len() {
+            return false;
+        }
+        // XDR-encoded strings carry an 8-byte header (type tag and length)
+        let end = 8 + prefix.len();
+        let value_bytes = value.clone().to_xdr(env);
+        let prefix_bytes = prefix.clone().to_xdr(env);
+        value_bytes.slice(8..end) == prefix_bytes.slice(8..end)
+    }
+
+    /// Reject metadata URIs outside the issuer's registered prefix
+    fn ensure_uri_allowed(env: &Env, issuer: &Address, uri: &String) -> Result<(), CertificateError> {
+        let prefix: Option<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::IssuerUriPrefix(issuer.clone()));
+        match prefix {
+            Some(prefix) if !Self::starts_with(env, uri, &prefix) => Err(CertificateError::InvalidData),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject certificate IDs outside the admin-configured format
+    fn ensure_id_format(env: &Env, id: &String) -> Result<(), CertificateError> {
+        let (prefix, max_length) = Self::get_certificate_id_format(env.clone());
+        if max_length > 0 && id.len() > max_length {
+            return Err(CertificateError::InvalidData);
+        }
+        if prefix.len() > 0 && !Self::starts_with(env, id, &prefix) {
+            return Err(CertificateError::InvalidData);
+        }
+        Ok(())
+    }
+
+    /// Reject recipients on the compliance deny-list
+    fn ensure_not_denied(env: &Env, address: &Address) -> Result<(), CertificateError> {
+        let denied: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Denylisted(address.clone()))
+            .unwrap_or(false);
+        if denied {
+            return Err(CertificateError::RecipientDenied);
+        }
+        Ok(())
+    }
+
+    /// Check that a certificate is in a state that allows a change of owner
+    fn ensure_transferable(cert: &Certificate) -> Result<(), CertificateError> {
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+        if cert.is_frozen() {
+            return Err(CertificateError::CertificateFrozen);
+        }
+        if cert.is_suspended() {
+            return Err(CertificateError::CertificateSuspended);
+        }
+        // Renounced certificates can no longer change hands
+        if cert.is_renounced() {
+            return Err(CertificateError::AlreadyRenounced);
+        }
+        Ok(())
+    }
+
+    /// Run the checks required before `recipient` can accept a transfer,
+    /// returning the transfer on success
+    fn check_acceptance(
+        env: &Env,
+        transfer_id: &String,
+        recipient: &Address,
+    ) -> Result<TransferRequest, CertificateError> {
+        let transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())))
+            .ok_or(CertificateError::TransferNotFound)?;
+
+        // Verify the recipient is the intended recipient
+        if transfer.to_address != *recipient {
+            return Err(CertificateError::Unauthorized);
+        }
+
+        // Check if transfer is still pending
+        if transfer.status != TransferStatus::Pending {
+            return Err(CertificateError::TransferNotPending);
+        }
+
+        // Pending transfers lapse once their TTL runs out
+        if Self::is_transfer_expired(env, &transfer) {
+            return Err(CertificateError::TransferExpired);
+        }
+
+        // The certificate must still be able to change hands
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&transfer.certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        Self::ensure_transferable(&cert)?;
+
+        Ok(transfer)
+    }
+
+    /// Run every check required before a certificate can change owner,
+    /// returning the certificate on success
+    fn validate_transfer(
+        env: &Env,
+        certificate_id: &String,
+        from_address: &Address,
+        to_address: &Address,
+    ) -> Result<Certificate, CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+
+        // Verify the sender is the current owner
+        if cert.owner != *from_address {
+            return Err(CertificateError::Unauthorized);
+        }
+
+        // Check if certificate is revoked, frozen or renounced
+        Self::ensure_transferable(&cert)?;
+
+        // Fresh certificates must age before they can change hands
+        if Self::is_too_new_to_transfer(env, &cert) {
+            return Err(CertificateError::CertificateTooNew);
+        }
+
+        // Check if recipient is different from sender, unless custody
+        // migrations between the same address are enabled
+        let allow_self_transfer: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::AllowSelfTransfer))
+            .unwrap_or(false);
+        if from_address == to_address && !allow_self_transfer {
+            return Err(CertificateError::SelfTransferNotAllowed);
+        }
+
+        Self::ensure_not_denied(env, to_address)?;
+
+        // Issuers may restrict where a certificate can go
+        let whitelist: Option<Vec<Address>> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferWhitelist(certificate_id.clone())));
+        if let Some(whitelist) = whitelist {
+            if !whitelist.contains(to_address) {
+                return Err(CertificateError::RecipientNotWhitelisted);
+            }
+        }
+
+        // Only one transfer may be in flight per certificate
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::Transfer(TransferKey::ActiveTransfer(certificate_id.clone())))
+        {
+            return Err(CertificateError::TransferAlreadyActive);
+        }
+
+        Ok(cert)
+    }
+
+    /// Check whether a certificate is still inside the minimum holding period
+    fn is_too_new_to_transfer(env: &Env, cert: &Certificate) -> bool {
+        let min_age: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MinAgeBeforeTransfer))
+            .unwrap_or(0);
+        env.ledger().timestamp() < cert.issued_at.saturating_add(min_age)
+    }
+
+    /// Clear the active transfer marker for a certificate
+    fn clear_active_transfer(env: &Env, certificate_id: &String) {
+        env.storage()
+            .instance()
+            .remove(&DataKey::Transfer(TransferKey::ActiveTransfer(certificate_id.clone())));
+    }
+
+    /// Check a backup URI is non-empty, new to the list and allowed for the issuer
+    fn ensure_backup_uri_allowed(
+        env: &Env,
+        issuer: &Address,
+        existing: &Vec<String>,
+        uri: &String,
+    ) -> Result<(), CertificateError> {
+        if uri.len() == 0 {
+            return Err(CertificateError::InvalidData);
+        }
+        if existing.contains(uri) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        Self::ensure_uri_allowed(env, issuer, uri)
+    }
+
+    /// Require the owner's authorization for an issuance when the admin has
+    /// turned on owner consent. Self-issued certificates are already covered
+    /// by the issuer's auth.
+    fn require_owner_consent(env: &Env, issuer: &Address, owner: &Address) {
+        let required: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::RequireOwnerConsent))
+            .unwrap_or(false);
+        if required && owner != issuer {
+            owner.require_auth();
+        }
+    }
+
+    /// Sum the weights of the co-issuers that have approved a revocation
+    fn approved_revocation_weight(config: &WeightedRevocationConfig, approvals: &Vec<Address>) -> u32 {
+        let mut total: u32 = 0;
+        for (signer, weight) in config.weights.iter() {
+            if approvals.contains(&signer) {
+                total = total.saturating_add(weight);
+            }
+        }
+        total
+    }
+
+    /// Check that a transfer of an org-owned certificate has gathered its
+    /// approval quorum. Certificates without approvers always pass. Transfers
+    /// without an ID, such as direct transfers, cannot gather approvals and
+    /// fail whenever a quorum is configured.
+    fn ensure_transfer_approved(
+        env: &Env,
+        certificate_id: &String,
+        transfer_id: Option<&String>,
+    ) -> Result<(), CertificateError> {
+        let config: Option<TransferApprovalConfig> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferApprovers(certificate_id.clone())));
+        let config = match config {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+
+        let approvals: Vec<Address> = match transfer_id {
+            Some(transfer_id) => env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::TransferApprovals(transfer_id.clone())))
+                .unwrap_or(Vec::new(env)),
+            None => Vec::new(env),
+        };
+        let approved = approvals.iter().filter(|a| config.approvers.contains(a)).count() as u32;
+        if approved < config.threshold {
+            return Err(CertificateError::InsufficientApprovals);
+        }
+        Ok(())
+    }
+
+    /// Take the reentrancy lock, failing if it is already held. Every path
+    /// that calls out to another contract (recipient hooks, the revocation
+    /// hook and fee token transfers) runs under this lock.
+    fn acquire_lock(env: &Env) -> Result<(), CertificateError> {
+        if env.storage().instance().get(&DataKey::Lock).unwrap_or(false) {
+            return Err(CertificateError::Reentrancy);
+        }
+        env.storage().instance().set(&DataKey::Lock, &true);
+        Ok(())
+    }
+
+    /// Release the reentrancy lock
+    fn release_lock(env: &Env) {
+        env.storage().instance().remove(&DataKey::Lock);
+    }
+
+    /// Move an accepted transfer to completed. Runs under the reentrancy lock
+    /// since it calls out to the recipient and the fee token.
+    fn finalize_transfer(
+        env: &Env,
+        transfer: TransferRequest,
+        cert: Certificate,
+    ) -> Result<(), CertificateError> {
+        Self::acquire_lock(env)?;
+        let result = Self::finalize_transfer_locked(env, transfer, cert);
+        Self::release_lock(env);
+        result
+    }
+
+    /// Update ownership, history and indexes for a completed transfer, and
+    /// emit the completion event
+    fn finalize_transfer_locked(
+        env: &Env,
+        mut transfer: TransferRequest,
+        mut cert: Certificate,
+    ) -> Result<(), CertificateError> {
+        Self::ensure_transfer_approved(env, &transfer.certificate_id, Some(&transfer.id))?;
+        Self::ensure_owner_capacity(env, &transfer.to_address)?;
+        
+        // Let a contract recipient react, reverting the transfer if it refuses
+        if transfer.notify_recipient {
+            Self::notify_recipient(env, &transfer)?;
+        }
+        
+        // Settle the fee if fee movement is configured
+        Self::settle_transfer_fee(env, &transfer, &cert.issuer);
+        
+        // Revoke certificate if required
+        if transfer.require_revocation {
+            Self::mark_revoked(
+                env,
+                &mut cert,
+                String::from_str(env, "Transferred to new owner"),
+                transfer.from_address.clone(),
+            );
+        }
+        
+        // Update certificate owner
+        cert.owner = transfer.to_address.clone();
+        Self::count_completed_transfer(env, &mut cert);
+        Self::save_certificate(env, &mut cert);
+        Self::move_owner_index(env, &transfer.certificate_id, &transfer.from_address, &transfer.to_address);
+        Self::emit_ownership_changed(
+            env,
+            &transfer.certificate_id,
+            transfer.from_address.clone(),
+            Some(transfer.to_address.clone()),
+            OwnershipChangeCause::Transfer,
+        );
+        
+        // Update transfer status to completed
+        Self::set_transfer_status(env, &mut transfer, TransferStatus::Completed);
+        transfer.completed_at = Some(env.ledger().timestamp());
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::TransferRequest(transfer.id.clone())), &transfer);
+        Self::clear_active_transfer(env, &transfer.certificate_id);
+        
+        // Add to transfer history
+        Self::record_transfer_history(
+            env,
+            TransferHistory {
+                transfer_id: transfer.id.clone(),
+                certificate_id: transfer.certificate_id.clone(),
+                from_address: transfer.from_address.clone(),
+                to_address: transfer.to_address.clone(),
+                transferred_at: transfer.completed_at.unwrap(),
+                transfer_fee: transfer.transfer_fee,
+                memo: transfer.memo.clone(),
+                data: transfer.data.clone(),
+            },
+        );
+        
+        // Emit transfer completed event
+        Self::publish_transfer_event(
+            env,
+            (symbol_short!("transfer_complete"), transfer.certificate_id.clone()),
+            TransferCompletedEvent {
+                transfer_id: transfer.id.clone(),
+                certificate_id: transfer.certificate_id,
+                from_address: transfer.from_address,
+                to_address: transfer.to_address,
+                completed_at: transfer.completed_at.unwrap(),
+                transfer_fee: transfer.transfer_fee,
+            },
+        );
+        
+        Ok(())
+    }
+
+    /// Count a completed change of owner against the certificate, revoking it
+    /// on the issuer's behalf once its transfer allowance is used up. Every
+    /// transfer completion path goes through here; callers save the certificate.
+    fn count_completed_transfer(env: &Env, cert: &mut Certificate) {
+        cert.transfer_count = cert.transfer_count.saturating_add(1);
+        if let Some(max_transfers) = cert.max_transfers {
+            if cert.transfer_count >= max_transfers && !cert.is_revoked() {
+                let issuer = cert.issuer.clone();
+                Self::mark_revoked(env, cert, String::from_str(env, "TransferLimitReached"), issuer);
+            }
+        }
+    }
+
+    /// Publish a transfer lifecycle event unless the admin has switched
+    /// transfer events off
+    fn publish_transfer_event<D>(env: &Env, topics: (Symbol, String), data: D)
+    where
+        D: IntoVal<Env, soroban_sdk::Val>,
+    {
+        let emit_events: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::EmitEvents))
+            .unwrap_or(true);
+        if emit_events {
+            env.events().publish(topics, data);
+        }
+    }
+
+    /// Compute the acceptance deadline for a transfer initiated at `initiated_at`
+    /// from the admin default TTL
+    fn default_transfer_expiry(env: &Env, initiated_at: u64) -> Option<u64> {
+        let ttl: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::DefaultTransferTtl))
+            .unwrap_or(0);
+        if ttl == 0 {
+            None
+        } else {
+            Some(initiated_at.saturating_add(ttl))
+        }
+    }
+
+    /// Check whether a transfer's acceptance deadline has passed
+    fn is_transfer_expired(env: &Env, transfer: &TransferRequest) -> bool {
+        // Inclusive, like `complete_by`: the deadline second itself still counts
+        match transfer.expires_at {
+            Some(expires_at) => env.ledger().timestamp() > expires_at,
+            None => false,
+        }
+    }
+
+    /// Move a transfer to a new status, keeping the per-status counts in step
+    fn set_transfer_status(env: &Env, transfer: &mut TransferRequest, status: TransferStatus) {
+        Self::count_transfer_status(env, Some(&transfer.status), &status);
+        transfer.status = status;
+    }
+
+    /// Shift one transfer from `from` (if any) to `to` in the status counts
+    fn count_transfer_status(env: &Env, from: Option<&TransferStatus>, to: &TransferStatus) {
+        let mut counts: StatusCounts = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::StatusCounts))
+            .unwrap_or_default();
+        if let Some(from) = from {
+            let count = Self::status_count_mut(&mut counts, from);
+            *count = count.saturating_sub(1);
+        }
+        let count = Self::status_count_mut(&mut counts, to);
+        *count = count.saturating_add(1);
+        env.storage().instance().set(&DataKey::Transfer(TransferKey::StatusCounts), &counts);
+    }
+
+    fn status_count_mut<'a>(counts: &'a mut StatusCounts, status: &TransferStatus) -> &'a mut u32 {
+        match status {
+            TransferStatus::Pending => &mut counts.pending,
+            TransferStatus::Accepted => &mut counts.accepted,
+            TransferStatus::Completed => &mut counts.completed,
+            TransferStatus::Rejected => &mut counts.rejected,
+            TransferStatus::Cancelled => &mut counts.cancelled,
+        }
+    }
+
+    /// Move a pending transfer to Accepted and announce it
+    fn record_acceptance(env: &Env, transfer: &mut TransferRequest) {
+        Self::set_transfer_status(env, transfer, TransferStatus::Accepted);
+        transfer.accepted_at = Some(env.ledger().timestamp());
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::TransferRequest(transfer.id.clone())), transfer);
+        
+        // Remove from pending transfers
+        Self::remove_pending_transfer(env, &transfer.to_address, &transfer.id);
+        
+        // Emit transfer accepted event
+        Self::publish_transfer_event(
+            env,
+            (symbol_short!("transfer_accept"), transfer.certificate_id.clone()),
+            TransferAcceptedEvent {
+                transfer_id: transfer.id.clone(),
+                accepted_at: transfer.accepted_at.unwrap(),
+            },
+        );
+    }
+
+    /// Check an executor against the transfer's completer policy
+    fn is_allowed_completer(transfer: &TransferRequest, cert: &Certificate, executor: &Address) -> bool {
+        match transfer.completer_policy {
+            CompleterPolicy::AnyParty => {
+                *executor == transfer.from_address
+                    || *executor == transfer.to_address
+                    || *executor == cert.issuer
+            }
+            CompleterPolicy::RecipientOnly => *executor == transfer.to_address,
+            CompleterPolicy::SenderOnly => *executor == transfer.from_address,
+            CompleterPolicy::IssuerOnly => *executor == cert.issuer,
+        }
+    }
+
+    /// Publish the unified ownership change event
+    fn emit_ownership_changed(
+        env: &Env,
+        id: &String,
+        old_owner: Address,
+        new_owner: Option<Address>,
+        cause: OwnershipChangeCause,
+    ) {
+        env.events().publish(
+            (symbol_short!("own_chg"), id.clone()),
+            OwnershipChangedEvent {
+                id: id.clone(),
+                old_owner,
+                new_owner,
+                at: env.ledger().timestamp(),
+                cause,
+            },
+        );
+    }
+
+    /// Remove a transfer id from an address's pending list
+    fn remove_pending_transfer(env: &Env, address: &Address, transfer_id: &String) {
+        let pending_key = DataKey::Transfer(TransferKey::PendingTransfers(address.clone()));
+        let pending_transfers: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&pending_key)
+            .unwrap_or(Vec::new(env));
+
+        let mut new_pending = Vec::new(env);
+        for pending_id in pending_transfers.iter() {
+            if pending_id != *transfer_id {
+                new_pending.push_back(pending_id.clone());
+            }
+        }
+
+        // Callers only remove transfers they know to be pending; a miss means
+        // the index has drifted and `repair_pending_index` should be run
+        debug_assert!(
+            new_pending.len() < pending_transfers.len(),
+            "transfer missing from pending index"
+        );
+        Self::decrement_pending_total(env, pending_transfers.len() - new_pending.len());
+        env.storage().instance().set(&pending_key, &new_pending);
+    }
+
+    /// Lower the global pending transfer count
+    fn decrement_pending_total(env: &Env, by: u32) {
+        if by == 0 {
+            return;
+        }
+        let total: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::PendingTransferTotal))
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::PendingTransferTotal), &total.saturating_sub(by));
+    }
+
+    /// Check whether an address belongs to a contract rather than an account
+    fn is_contract_address(env: &Env, addr: &Address) -> bool {
+        // XDR-encoded addresses carry a 4-byte ScVal tag followed by the
+        // 4-byte ScAddress type: 0 for accounts, 1 for contracts
+        let encoded = addr.clone().to_xdr(env);
+        encoded.get(7) == Some(1)
+    }
+
+    /// Invoke `on_certificate_received(from, certificate_id, transfer_id) -> bool`
+    /// on a contract recipient. Non-contract recipients are skipped.
+    fn notify_recipient(env: &Env, transfer: &TransferRequest) -> Result<(), CertificateError> {
+        if !Self::is_contract_address(env, &transfer.to_address) {
+            return Ok(());
+        }
+
+        let accepted = env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            &transfer.to_address,
+            &Symbol::new(env, "on_certificate_received"),
+            vec![
+                env,
+                transfer.from_address.into_val(env),
+                transfer.certificate_id.into_val(env),
+                transfer.id.into_val(env),
+            ],
+        );
+
+        match accepted {
+            Ok(Ok(true)) => Ok(()),
+            _ => Err(CertificateError::RecipientRejected),
+        }
+    }
+
+    /// Check an issuer's Ed25519 signature over a claim payload. Verified in
+    /// the contract rather than through the host's `ed25519_verify`, which
+    /// traps on a bad signature instead of returning an error.
+    fn verify_claim_signature(
+        public_key: &BytesN<32>,
+        payload: &Bytes,
+        signature: &BytesN<64>,
+    ) -> Result<(), CertificateError> {
+        if payload.len() > MAX_CLAIM_PAYLOAD_LENGTH {
+            return Err(CertificateError::InvalidData);
+        }
+        let mut message = [0u8; MAX_CLAIM_PAYLOAD_LENGTH as usize];
+        let len = payload.len() as usize;
+        payload.copy_into_slice(&mut message[..len]);
+
+        let key = ed25519_dalek::VerifyingKey::from_bytes(&public_key.to_array())
+            .map_err(|_| CertificateError::InvalidSignature)?;
+        let signature = ed25519_dalek::Signature::from_bytes(&signature.to_array());
+        key.verify_strict(&message[..len], &signature)
+            .map_err(|_| CertificateError::InvalidSignature)
+    }
+
+    /// Call the registered revocation hook, if any, under the reentrancy
+    /// lock. In strict mode a failing hook fails the revocation with
+    /// `RevocationHookFailed`; otherwise the failure is reported through a
+    /// `hook_fail` event and the revocation stands.
+    fn notify_revocation_hook(env: &Env, id: &String) -> Result<(), CertificateError> {
+        let hook: Address = match env.storage().instance().get(&DataKey::Config(ConfigKey::RevocationHook)) {
+            Some(hook) => hook,
+            None => return Ok(()),
+        };
+
+        Self::acquire_lock(env)?;
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &hook,
+            &Symbol::new(env, "on_certificate_revoked"),
+            vec![env, id.into_val(env)],
+        );
+        Self::release_lock(env);
+
+        if !matches!(result, Ok(Ok(()))) {
+            if Self::is_revocation_hook_strict(env.clone()) {
+                return Err(CertificateError::RevocationHookFailed);
+            }
+            env.events().publish((symbol_short!("hook_fail"), id.clone()), hook);
+        }
+        Ok(())
+    }
+
+    /// Split a transfer fee between the certificate issuer and the treasury.
+    /// Prepaid fees are released from escrow; otherwise the recipient pays.
+    /// No-op when the fee is zero or, for unpaid fees, no fee config is set.
+    fn settle_transfer_fee(env: &Env, transfer: &TransferRequest, issuer: &Address) {
+        if transfer.transfer_fee == 0 {
+            return;
+        }
+        let config: Option<FeeConfig> = env.storage().instance().get(&DataKey::Config(ConfigKey::FeeConfig));
+
+        let fee = transfer.transfer_fee;
+        let (payer, token_address) = match (&transfer.prepaid_token, &config) {
+            (Some(prepaid), _) => (env.current_contract_address(), prepaid.clone()),
+            (None, Some(config)) => (transfer.to_address.clone(), config.token.clone()),
+            (None, None) => return,
+        };
+
+        // Escrowed fees go entirely to the issuer if the treasury was unset since
+        let royalty = match &config {
+            Some(config) => fee * config.royalty_bps as i128 / BPS_DENOMINATOR as i128,
+            None => fee,
+        };
+        let platform = fee - royalty;
+
+        let token = token::Client::new(env, &token_address);
+        if royalty > 0 {
+            token.transfer(&payer, issuer, &royalty);
+        }
+        if let Some(config) = config {
+            if platform > 0 {
+                token.transfer(&payer, &config.treasury, &platform);
+            }
+        }
+    }
+
+    /// Return an escrowed fee to the sender of a transfer that will not
+    /// complete. Callers persist the transfer's final status first.
+    fn refund_prepaid_fee(env: &Env, transfer: &TransferRequest) -> Result<(), CertificateError> {
+        if let Some(ref prepaid) = transfer.prepaid_token {
+            Self::acquire_lock(env)?;
+            token::Client::new(env, prepaid).transfer(
+                &env.current_contract_address(),
+                &transfer.from_address,
+                &transfer.transfer_fee,
+            );
+            Self::release_lock(env);
+        }
+        Ok(())
+    }
+
+    /// Cancel a pending transfer that has passed its expiry, refunding any
+    /// escrowed fee. Callers check that the transfer is pending and expired.
+    fn close_expired_transfer(
+        env: &Env,
+        transfer_id: String,
+        mut transfer: TransferRequest,
+    ) -> Result<(), CertificateError> {
+        Self::set_transfer_status(env, &mut transfer, TransferStatus::Cancelled);
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())), &transfer);
+        Self::clear_active_transfer(env, &transfer.certificate_id);
+        Self::refund_prepaid_fee(env, &transfer)?;
+
+        Self::remove_pending_transfer(env, &transfer.to_address, &transfer_id);
+
+        Self::publish_transfer_event(
+            env,
+            (symbol_short!("transfer_expire"), transfer.certificate_id.clone()),
+            TransferExpiredEvent {
+                transfer_id,
+                certificate_id: transfer.certificate_id,
+                expired_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Record a certificate against its owner
+    fn add_to_owner_index(env: &Env, owner: &Address, certificate_id: &String) {
+        let key = DataKey::Index(IndexKey::OwnerCertificates(owner.clone()));
+        let mut ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(certificate_id.clone());
+        env.storage().instance().set(&key, &ids);
+    }
+
+    /// Insert a certificate into the expiry index, after any certificates
+    /// expiring at the same time
+    fn add_to_expiry_index(env: &Env, certificate_id: &String, expires_at: u64) {
+        let mut index: Vec<(u64, String)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::ExpiryIndex))
+            .unwrap_or(Vec::new(env));
+        let mut position = index.len();
+        for (i, (entry_expiry, _)) in index.iter().enumerate() {
+            if entry_expiry > expires_at {
+                position = i as u32;
+                break;
+            }
+        }
+        index.insert(position, (expires_at, certificate_id.clone()));
+        env.storage().instance().set(&DataKey::Index(IndexKey::ExpiryIndex), &index);
+    }
+
+    /// Record a certificate against its issuer
+    fn add_to_issuer_index(env: &Env, issuer: &Address, certificate_id: &String) {
+        let key = DataKey::Index(IndexKey::IssuerCertificates(issuer.clone()));
+        let mut ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(certificate_id.clone());
+        env.storage().instance().set(&key, &ids);
+    }
+
+    /// Remove a certificate from an issuer's index
+    fn remove_from_issuer_index(env: &Env, issuer: &Address, certificate_id: &String) {
+        let key = DataKey::Index(IndexKey::IssuerCertificates(issuer.clone()));
+        let ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let mut remaining = Vec::new(env);
+        for id in ids.iter() {
+            if id != *certificate_id {
+                remaining.push_back(id);
+            }
+        }
+        env.storage().instance().set(&key, &remaining);
+    }
+
+    /// Move a certificate from one owner's index to another's
+    fn move_owner_index(env: &Env, certificate_id: &String, from: &Address, to: &Address) {
+        let from_key = DataKey::Index(IndexKey::OwnerCertificates(from.clone()));
+        let ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&from_key)
+            .unwrap_or(Vec::new(env));
+        let mut remaining = Vec::new(env);
+        for id in ids.iter() {
+            if id != *certificate_id {
+                remaining.push_back(id);
+            }
+        }
+        env.storage().instance().set(&from_key, &remaining);
+
+        Self::add_to_owner_index(env, to, certificate_id);
+    }
+
+    /// Count the certificates an owner holds that are not revoked or renounced
+    fn count_active_certificates(env: &Env, owner: &Address) -> u32 {
+        let ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::OwnerCertificates(owner.clone())))
+            .unwrap_or(Vec::new(env));
+        let mut count = 0;
+        for id in ids.iter() {
+            if let Some(cert) = env.storage().instance().get::<String, Certificate>(&id) {
+                if !cert.is_revoked() && !cert.is_renounced() {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Fail if the owner already holds the configured maximum of active certificates
+    fn ensure_owner_capacity(env: &Env, owner: &Address) -> Result<(), CertificateError> {
+        let max: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MaxCertsPerOwner))
+            .unwrap_or(0);
+        if max > 0 && Self::count_active_certificates(env, owner) >= max {
+            return Err(CertificateError::OwnerLimitReached);
+        }
+        Ok(())
+    }
+
+    /// Load the stored admin and require its authorization
+    fn require_admin(env: &Env) -> Result<Address, CertificateError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CertificateError::Unauthorized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Append a completed transfer to the certificate's history and to the
+    /// aggregated history of both parties
+    fn record_transfer_history(env: &Env, entry: TransferHistory) {
+        let history_key = DataKey::Transfer(TransferKey::TransferHistory(entry.certificate_id.clone()));
+        Self::append_history(env, &history_key, &entry);
+
+        let from_key = DataKey::Transfer(TransferKey::AddressHistory(entry.from_address.clone()));
+        Self::append_history(env, &from_key, &entry);
+
+        let to_key = DataKey::Transfer(TransferKey::AddressHistory(entry.to_address.clone()));
+        Self::append_history(env, &to_key, &entry);
+    }
+
+    fn append_history(env: &Env, key: &DataKey, entry: &TransferHistory) {
+        let mut history: Vec<TransferHistory> = env
+            .storage()
+            .instance()
+            .get(key)
+            .unwrap_or(Vec::new(env));
+        history.push_back(entry.clone());
+        env.storage().instance().set(key, &history);
+    }
+
+    /// Build the transfer id recorded for a direct transfer, "direct-<sequence>"
+    fn direct_transfer_id(env: &Env, sequence: u64) -> String {
+        const PREFIX: &[u8] = b"direct-";
+        let mut buf = [0u8; 27]; // prefix plus the 20 digits of u64::MAX
+        buf[..PREFIX.len()].copy_from_slice(PREFIX);
+
+        let mut digits = [0u8; 20];
+        let mut count = 0;
+        let mut remaining = sequence;
+        loop {
+            digits[count] = b'0' + (remaining % 10) as u8;
+            count += 1;
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+        for i in 0..count {
+            buf[PREFIX.len() + i] = digits[count - 1 - i];
+        }
+        String::from_bytes(env, &buf[..PREFIX.len() + count])
+    }
+
+    /// Increment the global transfer counter
+    fn increment_transfer_count(env: &Env) -> Result<(), CertificateError> {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferCount))
+            .unwrap_or(0);
+        let next = count.checked_add(1).ok_or(CertificateError::CounterOverflow)?;
+        env.storage().instance().set(&DataKey::Transfer(TransferKey::TransferCount), &next);
+        Ok(())
+    }
+}
+
+#[contractimpl]
+impl CertificateContract {
+    /// Initialize the contract with an administrator
+    pub fn initialize(env: Env, admin: Address) -> Result<(), CertificateError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(CertificateError::AlreadyExists);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Get the contract administrator
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    /// Get the semantic version (major, minor, patch) of the deployed code
+    pub fn version(_env: Env) -> (u32, u32, u32) {
+        CONTRACT_VERSION
+    }
+
+    /// Admin diagnostic reporting whether a storage entry exists. `key_kind`
+    /// selects the key family and `key_str` its string identifier:
+    ///
+    /// * 0 - certificate
+    /// * 1 - transfer request
+    /// * 2 - active transfer of a certificate
+    /// * 3 - transfer history of a certificate
+    /// * 4 - transfer index of a certificate
+    /// * 5 - reserved transfer ID
+    /// * 6 - upgrade request
+    /// * 7 - frozen certificate
+    pub fn has_key(env: Env, key_kind: u32, key_str: String) -> Result<bool, CertificateError> {
+        Self::require_admin(&env)?;
+
+        let storage = env.storage().instance();
+        let present = match key_kind {
+            0 => storage.has(&key_str),
+            1 => storage.has(&DataKey::Transfer(TransferKey::TransferRequest(key_str))),
+            2 => storage.has(&DataKey::Transfer(TransferKey::ActiveTransfer(key_str))),
+            3 => storage.has(&DataKey::Transfer(TransferKey::TransferHistory(key_str))),
+            4 => storage.has(&DataKey::Transfer(TransferKey::CertTransfers(key_str))),
+            5 => storage.has(&DataKey::Transfer(TransferKey::UsedTransferId(key_str))),
+            6 => storage.has(&DataKey::UpgradeRequest(key_str)),
+            7 => storage.has(&DataKey::FrozenCertificate(key_str)),
+            _ => return Err(CertificateError::InvalidData),
+        };
+        Ok(present)
+    }
+
+    /// Get every admin-set parameter in one call
+    pub fn get_config(env: Env) -> ContractConfig {
+        let (min_transfer_fee, max_transfer_fee) = Self::get_transfer_fee_limits(env.clone());
+        let (max_transfers_per_window, rate_window_seconds) = Self::get_transfer_rate_limit(env.clone());
+        let (certificate_id_prefix, max_certificate_id_length) = Self::get_certificate_id_format(env.clone());
+
+        ContractConfig {
+            admin: Self::get_admin(env.clone()),
+            pending_admin: Self::get_pending_admin(env.clone()),
+            fee_config: Self::get_fee_config(env.clone()),
+            min_transfer_fee,
+            max_transfer_fee,
+            require_memo_on_fee: Self::is_memo_required_on_fee(env.clone()),
+            allow_self_transfer: Self::is_self_transfer_allowed(env.clone()),
+            max_certs_per_owner: Self::get_max_certs_per_owner(env.clone()),
+            max_transfers_per_window,
+            rate_window_seconds,
+            issuer_auto_transfer: Self::is_issuer_auto_transfer_enabled(env.clone()),
+            require_owner_consent_on_issue: Self::is_issue_consent_required(env.clone()),
+            revocation_hook: Self::get_revocation_hook(env.clone()),
+            strict_revocation_hook: Self::is_revocation_hook_strict(env.clone()),
+            emit_events: Self::are_events_enabled(env.clone()),
+            default_transfer_ttl: Self::get_default_transfer_ttl(env.clone()),
+            min_transfer_age_seconds: Self::get_min_age_before_transfer(env.clone()),
+            certificate_id_prefix,
+            max_certificate_id_length,
+        }
+    }
+
+    /// Propose a new administrator. The handover only takes effect once the
+    /// proposed address calls `accept_admin`.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::PendingAdmin, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending administrator proposal
+    pub fn accept_admin(env: Env) -> Result<(), CertificateError> {
+        let new_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(CertificateError::NotFound)?;
+        new_admin.require_auth();
+
+        let old_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(CertificateError::Unauthorized)?;
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (symbol_short!("admin_chg"),),
+            AdminChangedEvent {
+                old_admin,
+                new_admin,
+                changed_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Get the proposed administrator, if a handover is in progress
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Set the maximum number of active certificates an address may hold.
+    /// A value of 0 means unlimited.
+    pub fn set_max_certs_per_owner(env: Env, max: u32) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::MaxCertsPerOwner), &max);
+        Ok(())
+    }
+
+    /// Get the maximum number of active certificates per owner (0 = unlimited)
+    pub fn get_max_certs_per_owner(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MaxCertsPerOwner))
+            .unwrap_or(0)
+    }
+
+    /// Allow or forbid transfers where sender and recipient are the same
+    /// address, for custody migrations run through a relayer. Disabled by default.
+    pub fn set_allow_self_transfer(env: Env, allowed: bool) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::AllowSelfTransfer), &allowed);
+        Ok(())
+    }
+
+    /// Check whether self-transfers are currently permitted
+    pub fn is_self_transfer_allowed(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::AllowSelfTransfer))
+            .unwrap_or(false)
+    }
+
+    /// Limit how many transfers an owner may initiate within a sliding
+    /// window. A `max_transfers` of 0 disables the limit.
+    pub fn set_transfer_rate_limit(
+        env: Env,
+        max_transfers: u32,
+        window_seconds: u64,
+    ) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+
+        if max_transfers > 0 && window_seconds == 0 {
+            return Err(CertificateError::InvalidData);
+        }
+
+        env.storage().instance().set(&DataKey::Config(ConfigKey::MaxTransfersPerWindow), &max_transfers);
+        env.storage().instance().set(&DataKey::Config(ConfigKey::RateWindowSeconds), &window_seconds);
+        Ok(())
+    }
+
+    /// Get the transfer rate limit as (max transfers, window seconds)
+    pub fn get_transfer_rate_limit(env: Env) -> (u32, u64) {
+        let max_transfers: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MaxTransfersPerWindow))
+            .unwrap_or(0);
+        let window_seconds: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::RateWindowSeconds))
+            .unwrap_or(0);
+        (max_transfers, window_seconds)
+    }
+
+    /// Require new certificate IDs to start with `prefix` and be at most
+    /// `max_length` long, e.g. `cert-` for `cert-<number>` IDs. An empty
+    /// prefix and a `max_length` of 0 each disable their check.
+    pub fn set_certificate_id_format(
+        env: Env,
+        prefix: String,
+        max_length: u32,
+    ) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+
+        if max_length > MAX_CERTIFICATE_ID_LENGTH || (max_length > 0 && prefix.len() >= max_length) {
+            return Err(CertificateError::InvalidData);
+        }
+
+        env.storage().instance().set(&DataKey::Config(ConfigKey::CertificateIdPrefix), &prefix);
+        env.storage().instance().set(&DataKey::Config(ConfigKey::MaxCertificateIdLength), &max_length);
+        Ok(())
+    }
+
+    /// Get the certificate ID format as (required prefix, max length)
+    pub fn get_certificate_id_format(env: Env) -> (String, u32) {
+        let prefix: String = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::CertificateIdPrefix))
+            .unwrap_or(String::from_str(&env, ""));
+        let max_length: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MaxCertificateIdLength))
+            .unwrap_or(0);
+        (prefix, max_length)
+    }
+
+    /// Allow issuers to hand certificates they hold to their first owner
+    /// without waiting for acceptance
+    pub fn set_issuer_auto_transfer(env: Env, enabled: bool) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::IssuerAutoTransfer), &enabled);
+        Ok(())
+    }
+
+    /// Check whether issuer-originated transfers may skip acceptance
+    pub fn is_issuer_auto_transfer_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::IssuerAutoTransfer))
+            .unwrap_or(false)
+    }
+
+    /// Require owners to co-sign the issuance of their certificates, so none
+    /// can be forced onto an address without its consent
+    pub fn set_issue_consent_required(env: Env, required: bool) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::RequireOwnerConsent), &required);
+        Ok(())
+    }
+
+    /// Check whether issuance needs the owner's authorization
+    pub fn is_issue_consent_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::RequireOwnerConsent))
+            .unwrap_or(false)
+    }
+
+    /// Set how long after issuance a certificate must be held before it can
+    /// be transferred, to discourage flipping fresh credentials. 0 disables it.
+    pub fn set_min_age_before_transfer(env: Env, seconds: u64) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::MinAgeBeforeTransfer), &seconds);
+        Ok(())
+    }
+
+    /// Get the minimum holding period before a certificate can be transferred
+    pub fn get_min_age_before_transfer(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MinAgeBeforeTransfer))
+            .unwrap_or(0)
+    }
+
+    /// Register a contract to be notified through
+    /// `on_certificate_revoked(id)` whenever a certificate is revoked. With
+    /// `strict`, a failing hook reverts the revocation; otherwise failures
+    /// are reported and the revocation stands. Passing `None` removes the hook.
+    pub fn set_revocation_hook(env: Env, hook: Option<Address>, strict: bool) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        match hook {
+            Some(hook) => env.storage().instance().set(&DataKey::Config(ConfigKey::RevocationHook), &hook),
+            None => env.storage().instance().remove(&DataKey::Config(ConfigKey::RevocationHook)),
+        }
+        env.storage().instance().set(&DataKey::Config(ConfigKey::StrictRevocationHook), &strict);
+        Ok(())
+    }
+
+    /// Get the contract notified on revocation, if any
+    pub fn get_revocation_hook(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Config(ConfigKey::RevocationHook))
+    }
+
+    /// Check whether a failing revocation hook reverts the revocation
+    pub fn is_revocation_hook_strict(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::StrictRevocationHook))
+            .unwrap_or(false)
+    }
+
+    /// Set how long transfers may stay pending when the sender does not set
+    /// their own TTL. A value of 0 means pending transfers never expire.
+    pub fn set_default_transfer_ttl(env: Env, seconds: u64) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::DefaultTransferTtl), &seconds);
+        Ok(())
+    }
+
+    /// Get the default pending transfer TTL in seconds (0 = no expiry)
+    pub fn get_default_transfer_ttl(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::DefaultTransferTtl))
+            .unwrap_or(0)
+    }
+
+    /// Turn transfer lifecycle events (initiated, accepted, completed,
+    /// rejected, cancelled) on or off.
+    ///
+    /// Switching them off saves resources on high-throughput deployments,
+    /// but indexers then see transfers only through state queries and
+    /// ownership change events. Transfer state is updated either way.
+    pub fn set_emit_events(env: Env, enabled: bool) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::EmitEvents), &enabled);
+        Ok(())
+    }
+
+    /// Check whether transfer lifecycle events are published
+    pub fn are_events_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::EmitEvents))
+            .unwrap_or(true)
+    }
+
+    /// Require a memo on every transfer that carries a fee
+    pub fn set_require_memo_on_fee(env: Env, required: bool) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Config(ConfigKey::RequireMemoOnFee), &required);
+        Ok(())
+    }
+
+    /// Check whether fee-bearing transfers must carry a memo
+    pub fn is_memo_required_on_fee(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::RequireMemoOnFee))
+            .unwrap_or(false)
+    }
+
+    /// Block an address from being issued or receiving certificates
+    pub fn add_to_denylist(env: Env, address: Address) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Denylisted(address), &true);
+        Ok(())
+    }
+
+    /// Lift a deny-list block on an address
+    pub fn remove_from_denylist(env: Env, address: Address) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        env.storage().instance().remove(&DataKey::Denylisted(address));
+        Ok(())
+    }
+
+    /// Check whether an address is on the deny-list
+    pub fn is_denylisted(env: Env, address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Denylisted(address))
+            .unwrap_or(false)
+    }
+
+    /// Configure how transfer fees are paid and split. `royalty_bps` is the
+    /// issuer's share in basis points; the remainder goes to the treasury.
+    pub fn set_fee_config(
+        env: Env,
+        token: Address,
+        treasury: Address,
+        royalty_bps: u32,
+    ) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+
+        if royalty_bps > BPS_DENOMINATOR {
+            return Err(CertificateError::InvalidData);
+        }
+
+        env.storage().instance().set(
+            &DataKey::Config(ConfigKey::FeeConfig),
+            &FeeConfig {
+                token,
+                treasury,
+                royalty_bps,
+            },
+        );
+        Ok(())
+    }
+
+    /// Get the fee settlement configuration, if any
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        env.storage().instance().get(&DataKey::Config(ConfigKey::FeeConfig))
+    }
+
+    /// Set the range of transfer fees accepted at initiation. A `max_fee` of
+    /// 0 means no cap.
+    pub fn set_transfer_fee_limits(env: Env, min_fee: i128, max_fee: i128) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+
+        if min_fee < 0 || max_fee < 0 || (max_fee != 0 && min_fee > max_fee) {
+            return Err(CertificateError::InvalidData);
+        }
+
+        env.storage().instance().set(&DataKey::Config(ConfigKey::MinTransferFee), &min_fee);
+        env.storage().instance().set(&DataKey::Config(ConfigKey::MaxTransferFee), &max_fee);
+        Ok(())
+    }
+
+    /// Get the accepted transfer fee range as (min, max); a max of 0 means no cap
+    pub fn get_transfer_fee_limits(env: Env) -> (i128, i128) {
+        let min_fee: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MinTransferFee))
+            .unwrap_or(0);
+        let max_fee: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Config(ConfigKey::MaxTransferFee))
+            .unwrap_or(0);
+        (min_fee, max_fee)
+    }
+
+    pub fn issue_certificate(
+        env: Env,
+        id: String,
+        issuer: Address,
+        owner: Address,
+        metadata_uri: String,
+        metadata_hash: Option<BytesN<32>>,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+
+        Self::store_new_certificate(&env, id, issuer, owner, metadata_uri, metadata_hash, None, false, Vec::new(&env))
+    }
+
+    /// Issue a certificate unless it already exists, for safe retries.
+    /// Returns `true` if it was issued and `false` if an identical certificate
+    /// was already there. A different certificate under the same ID is an
+    /// `InvalidData` error.
+    pub fn issue_certificate_idempotent(
+        env: Env,
+        id: String,
+        issuer: Address,
+        owner: Address,
+        metadata_uri: String,
+        metadata_hash: Option<BytesN<32>>,
+    ) -> Result<bool, CertificateError> {
+        issuer.require_auth();
+
+        let existing: Option<Certificate> = env.storage().instance().get(&id);
+        if let Some(cert) = existing {
+            let matches = cert.issuer == issuer
+                && cert.owner == owner
+                && cert.metadata_uri == metadata_uri
+                && cert.metadata_hash == metadata_hash;
+            return if matches { Ok(false) } else { Err(CertificateError::InvalidData) };
+        }
+
+        Self::store_new_certificate(&env, id, issuer, owner, metadata_uri, metadata_hash, None, false, Vec::new(&env))?;
+        Ok(true)
+    }
+
+    /// Issue a certificate with backup metadata URIs that verifiers can fall
+    /// back to when the primary is unreachable
+    pub fn issue_certificate_with_backups(
+        env: Env,
+        id: String,
+        issuer: Address,
+        owner: Address,
+        metadata_uri: String,
+        metadata_hash: Option<BytesN<32>>,
+        backup_uris: Vec<String>,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+
+        if backup_uris.len() > MAX_BACKUP_URIS {
+            return Err(CertificateError::InvalidData);
+        }
+        let mut checked: Vec<String> = Vec::new(&env);
+        for uri in backup_uris.iter() {
+            Self::ensure_backup_uri_allowed(&env, &issuer, &checked, &uri)?;
+            checked.push_back(uri);
+        }
+
+        Self::store_new_certificate(
+            &env,
+            id,
+            issuer,
+            owner,
+            metadata_uri,
+            metadata_hash,
+            None,
+            false,
+            backup_uris,
+        )
+    }
+
+    /// Issue a certificate to an initial owner and open a transfer from them
+    /// to the final owner in the same transaction. Requires the auth of both
+    /// the issuer and the initial owner.
+    pub fn issue_and_initiate_transfer(
+        env: Env,
+        id: String,
+        issuer: Address,
+        initial_owner: Address,
+        metadata_uri: String,
+        metadata_hash: Option<BytesN<32>>,
+        transfer_id: String,
+        final_owner: Address,
+        memo: Option<String>,
+    ) -> Result<(), CertificateError> {
+        Self::issue_certificate(
+            env.clone(),
+            id.clone(),
+            issuer,
+            initial_owner.clone(),
+            metadata_uri,
+            metadata_hash,
+        )?;
+
+        Self::initiate_transfer_v2(
+            env,
+            transfer_id,
+            id,
+            initial_owner,
+            final_owner,
+            false,
+            0,
+            memo,
+            false,
+            None,
+            None,
+        )
+    }
+
+    /// Issue a certificate that expires at the given ledger timestamp
+    ///
+    /// # Arguments
+    /// * `expires_at` - Timestamp at which the certificate stops being valid
+    /// * `auto_revoke_on_expiry` - Report the certificate as revoked once expired.
+    ///   This is a read-time interpretation; storage is never mutated on expiry.
+    pub fn issue_certificate_with_expiry(
+        env: Env,
+        id: String,
+        issuer: Address,
+        owner: Address,
+        metadata_uri: String,
+        expires_at: u64,
+        auto_revoke_on_expiry: bool,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+
+        if expires_at <= env.ledger().timestamp() {
+            return Err(CertificateError::InvalidData);
+        }
+
+        Self::store_new_certificate(
+            &env,
+            id,
+            issuer,
+            owner,
+            metadata_uri,
+            None,
+            Some(expires_at),
+            auto_revoke_on_expiry,
+            Vec::new(&env),
+        )
+    }
+
+    pub fn revoke_certificate(env: Env, id: String, reason: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        let issuer = cert.issuer.clone();
+        Self::mark_revoked(&env, &mut cert, reason, issuer);
+
+        Self::save_certificate(&env, &mut cert);
+        Self::notify_revocation_hook(&env, &id)
+    }
+
+    /// Replace a certificate with a new one issued to the same owner. The old
+    /// certificate is revoked as `Superseded` and both carry lineage links.
+    pub fn reissue_certificate(
+        env: Env,
+        old_id: String,
+        new_id: String,
+        new_metadata_uri: String,
+    ) -> Result<(), CertificateError> {
+        let mut old_cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&old_id)
+            .ok_or(CertificateError::NotFound)?;
+
+        old_cert.issuer.require_auth();
+
+        if old_cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+        if env.storage().instance().has(&DataKey::Transfer(TransferKey::ActiveTransfer(old_id.clone()))) {
+            return Err(CertificateError::TransferAlreadyActive);
+        }
+
+        // Revoke first so the replacement does not count against owner limits
+        let issuer = old_cert.issuer.clone();
+        Self::mark_revoked(&env, &mut old_cert, String::from_str(&env, "Superseded"), issuer.clone());
+        old_cert.superseded_by = Some(new_id.clone());
+        Self::save_certificate(&env, &mut old_cert);
+
+        Self::store_new_certificate(
+            &env,
+            new_id.clone(),
+            issuer,
+            old_cert.owner.clone(),
+            new_metadata_uri,
+            None,
+            old_cert.expires_at,
+            old_cert.auto_revoke_on_expiry(),
+            Vec::new(&env),
+        )?;
+
+        let mut new_cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&new_id)
+            .ok_or(CertificateError::NotFound)?;
+        new_cert.supersedes = Some(old_id);
+        Self::save_certificate(&env, &mut new_cert);
+
+        Ok(())
+    }
+
+    /// Preview the certificate as it would look after `revoke_certificate`,
+    /// without persisting anything or requiring auth
+    pub fn simulate_revoke(env: Env, id: String, reason: String) -> Result<Certificate, CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        let issuer = cert.issuer.clone();
+        Self::apply_revocation(&env, &mut cert, reason, issuer);
+        Self::touch_certificate(&env, &mut cert);
+
+        Ok(cert)
+    }
+
+    /// Schedule a revocation that takes effect at a future timestamp. Until
+    /// then the certificate verifies normally; afterwards it reads as revoked.
+    pub fn schedule_revocation(
+        env: Env,
+        id: String,
+        effective_at: u64,
+        reason: String,
+    ) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.is_revoked() || Self::is_scheduled_revocation_due(&env, &cert) {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+        if effective_at <= env.ledger().timestamp() {
+            return Err(CertificateError::InvalidData);
+        }
+
+        cert.scheduled_revocation_at = Some(effective_at);
+        cert.scheduled_revocation_reason = Some(reason);
+        Self::save_certificate(&env, &mut cert);
+        env.storage().instance().remove(&DataKey::Revocation(RevocationKey::RevocationGrace(id)));
+
+        Ok(())
+    }
+
+    /// Revoke a certificate after a grace period during which the owner may
+    /// appeal with `appeal_revocation`
+    pub fn revoke_with_grace(
+        env: Env,
+        id: String,
+        reason: String,
+        grace_seconds: u64,
+    ) -> Result<(), CertificateError> {
+        let effective_at = env.ledger().timestamp().saturating_add(grace_seconds);
+        Self::schedule_revocation(env.clone(), id.clone(), effective_at, reason)?;
+        env.storage().instance().set(&DataKey::Revocation(RevocationKey::RevocationGrace(id)), &true);
+        Ok(())
+    }
+
+    /// Record a scheduled revocation that has taken effect as a revocation,
+    /// adding it to the revocation index and the supply counters. Anyone may
+    /// call this once the scheduled time has passed.
+    pub fn apply_scheduled_revocation(env: Env, id: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+        let effective_at = cert.scheduled_revocation_at.ok_or(CertificateError::NotFound)?;
+        if !Self::is_scheduled_revocation_due(&env, &cert) {
+            return Err(CertificateError::NotRevoked);
+        }
+
+        let reason = cert
+            .scheduled_revocation_reason
+            .take()
+            .unwrap_or(String::from_str(&env, "Scheduled revocation"));
+        let issuer = cert.issuer.clone();
+        Self::mark_revoked(&env, &mut cert, reason, issuer);
+        cert.revoked_at = Some(effective_at);
+        cert.scheduled_revocation_at = None;
+        Self::save_certificate(&env, &mut cert);
+        env.storage().instance().remove(&DataKey::Revocation(RevocationKey::RevocationGrace(id)));
+
+        Ok(())
+    }
+
+    /// Appeal a grace-period revocation before it takes effect. The
+    /// revocation is withdrawn and the appeal recorded.
+    pub fn appeal_revocation(env: Env, id: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.owner.require_auth();
+
+        let grace_key = DataKey::Revocation(RevocationKey::RevocationGrace(id.clone()));
+        if !env.storage().instance().has(&grace_key) || cert.scheduled_revocation_at.is_none() {
+            return Err(CertificateError::NotFound);
+        }
+        if Self::is_scheduled_revocation_due(&env, &cert) {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        let appeal = RevocationAppeal {
+            appealed_by: cert.owner.clone(),
+            appealed_at: env.ledger().timestamp(),
+            reason: cert.scheduled_revocation_reason.clone(),
+        };
+
+        cert.scheduled_revocation_at = None;
+        cert.scheduled_revocation_reason = None;
+        Self::save_certificate(&env, &mut cert);
+        env.storage().instance().remove(&grace_key);
+        env.storage().instance().set(&DataKey::Revocation(RevocationKey::RevocationAppeal(id)), &appeal);
+
+        Ok(())
+    }
+
+    /// Get the most recent revocation appeal for a certificate
+    pub fn get_revocation_appeal(env: Env, id: String) -> Option<RevocationAppeal> {
+        env.storage().instance().get(&DataKey::Revocation(RevocationKey::RevocationAppeal(id)))
+    }
+
+    /// Contest a revocation on-chain. Disputes must be filed within the
+    /// challenge period following the revocation, and only once.
+    pub fn dispute_revocation(
+        env: Env,
+        id: String,
+        owner: Address,
+        statement: String,
+    ) -> Result<(), CertificateError> {
+        owner.require_auth();
+
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        if cert.owner != owner {
+            return Err(CertificateError::Unauthorized);
+        }
+        let revoked_at = match cert.revoked_at {
+            Some(revoked_at) if cert.is_revoked() => revoked_at,
+            _ => return Err(CertificateError::NotRevoked),
+        };
+        let now = env.ledger().timestamp();
+        if now > revoked_at.saturating_add(DISPUTE_WINDOW_SECONDS) {
+            return Err(CertificateError::DisputeWindowClosed);
+        }
+
+        let key = DataKey::Revocation(RevocationKey::Dispute(id));
+        if env.storage().instance().has(&key) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        env.storage().instance().set(
+            &key,
+            &RevocationDispute {
+                disputed_by: owner,
+                statement,
+                disputed_at: now,
+                resolved_by: None,
+                resolved_at: None,
+                upheld: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Resolve an open dispute as the certificate's issuer or the admin.
+    /// Upholding keeps the revocation; otherwise the certificate is reinstated.
+    pub fn resolve_dispute(
+        env: Env,
+        id: String,
+        resolver: Address,
+        uphold: bool,
+    ) -> Result<(), CertificateError> {
+        resolver.require_auth();
+
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if resolver != cert.issuer && Some(resolver.clone()) != admin {
+            return Err(CertificateError::Unauthorized);
+        }
+
+        let key = DataKey::Revocation(RevocationKey::Dispute(id.clone()));
+        let mut dispute: RevocationDispute = env
+            .storage()
+            .instance()
+            .get(&key)
+            .ok_or(CertificateError::NotFound)?;
+        if dispute.upheld.is_some() {
+            return Err(CertificateError::InvalidData);
+        }
+
+        let now = env.ledger().timestamp();
+        if !uphold {
+            cert.set_flag(FLAG_REVOKED, false);
+            cert.revocation_reason = None;
+            cert.revoked_at = None;
+            cert.revoked_by = None;
+            Self::save_certificate(&env, &mut cert);
+
+            // Count before unindexing, so a first-time seed still includes it
+            Self::adjust_supply(&env, DataKey::Index(IndexKey::RevokedCount), false);
+            Self::adjust_supply(&env, DataKey::Index(IndexKey::ActiveCount), true);
+            let mut index: Vec<String> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Index(IndexKey::RevokedIndex))
+                .unwrap_or(Vec::new(&env));
+            if let Some(pos) = index.first_index_of(&id) {
+                index.remove(pos);
+                env.storage().instance().set(&DataKey::Index(IndexKey::RevokedIndex), &index);
+            }
+
+            env.events().publish(
+                (symbol_short!("reinstate"),),
+                CertificateReinstatedEvent {
+                    certificate_id: id,
+                    reinstated_by: resolver.clone(),
+                    reinstated_at: now,
+                },
+            );
+        }
+
+        dispute.resolved_by = Some(resolver);
+        dispute.resolved_at = Some(now);
+        dispute.upheld = Some(uphold);
+        env.storage().instance().set(&key, &dispute);
+
+        Ok(())
+    }
+
+    /// Get the revocation dispute recorded for a certificate
+    pub fn get_dispute(env: Env, id: String) -> Option<RevocationDispute> {
+        env.storage().instance().get(&DataKey::Revocation(RevocationKey::Dispute(id)))
+    }
+
+    /// Require a weighted quorum of co-issuers to revoke a certificate.
+    /// Replacing the weights discards any approvals gathered so far.
+    pub fn set_revocation_weights(
+        env: Env,
+        id: String,
+        weights: Vec<(Address, u32)>,
+        weight_threshold: u32,
+    ) -> Result<(), CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        // The threshold must be reachable and every co-issuer must count
+        let mut total: u32 = 0;
+        for (_, weight) in weights.iter() {
+            if weight == 0 {
+                return Err(CertificateError::InvalidData);
+            }
+            total = total.saturating_add(weight);
+        }
+        if weight_threshold == 0 || weight_threshold > total {
+            return Err(CertificateError::InvalidData);
+        }
+
+        env.storage().instance().set(
+            &DataKey::Revocation(RevocationKey::RevocationWeights(id.clone())),
+            &WeightedRevocationConfig { weights, weight_threshold },
+        );
+        env.storage().instance().remove(&DataKey::Revocation(RevocationKey::RevocationApprovals(id)));
+
+        Ok(())
+    }
+
+    /// Get the co-issuer weights and threshold for revoking a certificate
+    pub fn get_revocation_weights(env: Env, id: String) -> Option<WeightedRevocationConfig> {
+        env.storage().instance().get(&DataKey::Revocation(RevocationKey::RevocationWeights(id)))
+    }
+
+    /// Approve the revocation of a certificate as a weighted co-issuer. The
+    /// certificate is revoked once the approving weight meets the threshold.
+    /// Returns whether this approval revoked it.
+    pub fn approve_weighted_revocation(
+        env: Env,
+        id: String,
+        approver: Address,
+        reason: String,
+    ) -> Result<bool, CertificateError> {
+        approver.require_auth();
+
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+        let config: WeightedRevocationConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::Revocation(RevocationKey::RevocationWeights(id.clone())))
+            .ok_or(CertificateError::NotFound)?;
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+        if !config.weights.iter().any(|(signer, _)| signer == approver) {
+            return Err(CertificateError::Unauthorized);
+        }
+
+        let approvals_key = DataKey::Revocation(RevocationKey::RevocationApprovals(id.clone()));
+        let mut approvals: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&approvals_key)
+            .unwrap_or(Vec::new(&env));
+        if approvals.contains(&approver) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        approvals.push_back(approver.clone());
+
+        let approved_weight = Self::approved_revocation_weight(&config, &approvals);
+        if approved_weight < config.weight_threshold {
+            env.storage().instance().set(&approvals_key, &approvals);
+            return Ok(false);
+        }
+
+        Self::mark_revoked(&env, &mut cert, reason, approver);
+        Self::save_certificate(&env, &mut cert);
+        env.storage().instance().remove(&approvals_key);
+
+        Ok(true)
+    }
+
+    /// Get the summed weight of the co-issuers approving a revocation so far
+    pub fn get_revocation_approval_weight(env: Env, id: String) -> u32 {
+        let config: Option<WeightedRevocationConfig> =
+            env.storage().instance().get(&DataKey::Revocation(RevocationKey::RevocationWeights(id.clone())));
+        let approvals: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Revocation(RevocationKey::RevocationApprovals(id)))
+            .unwrap_or(Vec::new(&env));
+        match config {
+            Some(config) => Self::approved_revocation_weight(&config, &approvals),
+            None => 0,
+        }
+    }
+
+    /// Cancel a scheduled revocation that has not yet taken effect
+    pub fn cancel_scheduled_revocation(env: Env, id: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.scheduled_revocation_at.is_none() {
+            return Err(CertificateError::NotFound);
+        }
+        if Self::is_scheduled_revocation_due(&env, &cert) {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        cert.scheduled_revocation_at = None;
+        cert.scheduled_revocation_reason = None;
+        Self::save_certificate(&env, &mut cert);
+        env.storage().instance().remove(&DataKey::Revocation(RevocationKey::RevocationGrace(id)));
+
+        Ok(())
+    }
+
+    /// Suspend a certificate as a temporary hold distinct from revocation.
+    /// Suspended certificates cannot be transferred and verify as `Suspended`.
+    pub fn suspend_certificate(
+        env: Env,
+        id: String,
+        reason: String,
+    ) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+        if cert.is_suspended() {
+            return Err(CertificateError::AlreadySuspended);
+        }
+
+        cert.set_flag(FLAG_SUSPENDED, true);
+        cert.suspension_reason = Some(reason.clone());
+        Self::save_certificate(&env, &mut cert);
+
+        env.events().publish(
+            (symbol_short!("suspend"),),
+            CertificateSuspendedEvent {
+                certificate_id: id,
+                suspended_by: cert.issuer,
+                suspended_at: env.ledger().timestamp(),
+                reason,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lift a suspension, returning the certificate to normal use
+    pub fn reinstate_from_suspension(env: Env, id: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if !cert.is_suspended() {
+            return Err(CertificateError::NotSuspended);
+        }
+
+        cert.set_flag(FLAG_SUSPENDED, false);
+        cert.suspension_reason = None;
+        Self::save_certificate(&env, &mut cert);
+
+        env.events().publish(
+            (symbol_short!("reinstate"),),
+            CertificateReinstatedEvent {
+                certificate_id: id,
+                reinstated_by: cert.issuer,
+                reinstated_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Update a certificate's metadata URI, failing if the certificate has been
+    /// modified since the caller read it
+    ///
+    /// # Arguments
+    /// * `id` - Certificate ID
+    /// * `new_uri` - Replacement metadata URI
+    /// * `expected_nonce` - Nonce the caller last observed on the certificate
+    /// * `new_hash` - Content hash of the new metadata
+    pub fn update_metadata_uri_checked(
+        env: Env,
+        id: String,
+        new_uri: String,
+        expected_nonce: u32,
+        new_hash: Option<BytesN<32>>,
+    ) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.nonce != expected_nonce {
+            return Err(CertificateError::VersionMismatch);
+        }
+        if new_uri.len() == 0 {
+            return Err(CertificateError::InvalidData);
+        }
+        Self::ensure_uri_allowed(&env, &cert.issuer, &new_uri)?;
+
+        // Keep the hash lookup index pointing at this certificate
+        if new_hash != cert.metadata_hash {
+            if let Some(ref hash) = new_hash {
+                if env.storage().instance().has(&DataKey::Index(IndexKey::HashToId(hash.clone()))) {
+                    return Err(CertificateError::AlreadyExists);
+                }
+                env.storage().instance().set(&DataKey::Index(IndexKey::HashToId(hash.clone())), &id);
+            }
+            if let Some(ref old_hash) = cert.metadata_hash {
+                env.storage().instance().remove(&DataKey::Index(IndexKey::HashToId(old_hash.clone())));
+            }
+        }
+
+        cert.metadata_uri = new_uri;
+        cert.metadata_hash = new_hash;
+        Self::save_certificate(&env, &mut cert);
+
+        Ok(())
+    }
+
+    /// Check a hash of fetched metadata content against the hash recorded on
+    /// the certificate. Returns false if no hash was recorded.
+    pub fn verify_metadata(
+        env: Env,
+        id: String,
+        content_hash: BytesN<32>,
+    ) -> Result<bool, CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+        Ok(cert.metadata_hash == Some(content_hash))
+    }
+
+    /// Register the prefix all of an issuer's metadata URIs must start with,
+    /// e.g. `ipfs://` or the issuer's domain. An empty prefix removes it.
+    pub fn set_issuer_uri_prefix(env: Env, issuer: Address, prefix: String) {
+        issuer.require_auth();
+
+        let key = DataKey::IssuerUriPrefix(issuer);
+        if prefix.len() == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &prefix);
+        }
+    }
+
+    /// Get the metadata URI prefix registered by an issuer
+    pub fn get_issuer_uri_prefix(env: Env, issuer: Address) -> Option<String> {
+        env.storage().instance().get(&DataKey::IssuerUriPrefix(issuer))
+    }
+
+    /// Add a backup metadata URI to a certificate, after any existing ones
+    pub fn add_backup_uri(env: Env, id: String, uri: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.backup_uris.len() >= MAX_BACKUP_URIS {
+            return Err(CertificateError::InvalidData);
+        }
+        Self::ensure_backup_uri_allowed(&env, &cert.issuer, &cert.backup_uris, &uri)?;
+
+        cert.backup_uris.push_back(uri);
+        Self::save_certificate(&env, &mut cert);
+        Ok(())
+    }
+
+    /// Remove a backup metadata URI from a certificate
+    pub fn remove_backup_uri(env: Env, id: String, uri: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        let index = cert
+            .backup_uris
+            .first_index_of(&uri)
+            .ok_or(CertificateError::NotFound)?;
+        cert.backup_uris.remove(index);
+        Self::save_certificate(&env, &mut cert);
+        Ok(())
+    }
+
+    /// Register a trusted third-party verifier contract under a display name
+    pub fn register_verifier(env: Env, verifier: Address, name: String) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+
+        if name.len() == 0 {
+            return Err(CertificateError::InvalidData);
+        }
+
+        let mut verifiers = Self::get_verifiers(env.clone());
+        if verifiers.iter().any(|(registered, _)| registered == verifier) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        verifiers.push_back((verifier, name));
+        env.storage().instance().set(&DataKey::Verifiers, &verifiers);
+        Ok(())
+    }
+
+    /// Get the registered verifier contracts with their names, in registration order
+    pub fn get_verifiers(env: Env) -> Vec<(Address, String)> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Verifiers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Point a certificate at a registered verifier contract, or clear it
+    /// with `None`. Only the owner may choose the verifier.
+    pub fn set_certificate_verifier(env: Env, id: String, verifier: Option<Address>) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.owner.require_auth();
+
+        if let Some(ref verifier) = verifier {
+            if !Self::get_verifiers(env.clone()).iter().any(|(registered, _)| registered == *verifier) {
+                return Err(CertificateError::NotFound);
+            }
+        }
+
+        cert.verifier = verifier;
+        Self::save_certificate(&env, &mut cert);
+        Ok(())
+    }
+
+    /// Limit how many completed transfers a certificate allows. The transfer
+    /// that reaches the limit revokes it with reason `TransferLimitReached`.
+    /// Passing `None` removes the limit.
+    pub fn set_max_transfers(env: Env, id: String, max_transfers: Option<u32>) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if let Some(max_transfers) = max_transfers {
+            if max_transfers == 0 || max_transfers <= cert.transfer_count {
+                return Err(CertificateError::InvalidData);
+            }
+        }
+
+        cert.max_transfers = max_transfers;
+        Self::save_certificate(&env, &mut cert);
+        Ok(())
+    }
+
+    /// Attach a free-form tag to a certificate
+    pub fn add_tag(env: Env, id: String, tag: String) -> Result<(), CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if tag.len() == 0 || tag.len() > MAX_TAG_LENGTH {
+            return Err(CertificateError::InvalidData);
+        }
+
+        let tags_key = DataKey::Index(IndexKey::CertificateTags(id.clone()));
+        let mut tags: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&tags_key)
+            .unwrap_or(Vec::new(&env));
+        if tags.contains(&tag) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        if tags.len() >= MAX_TAGS_PER_CERTIFICATE {
+            return Err(CertificateError::InvalidData);
+        }
+        tags.push_back(tag.clone());
+        env.storage().instance().set(&tags_key, &tags);
+
+        let index_key = DataKey::Index(IndexKey::TagIndex(tag));
+        let mut ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(id);
+        env.storage().instance().set(&index_key, &ids);
+
+        Ok(())
+    }
+
+    /// Remove a tag from a certificate
+    pub fn remove_tag(env: Env, id: String, tag: String) -> Result<(), CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        let tags_key = DataKey::Index(IndexKey::CertificateTags(id.clone()));
+        let mut tags: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&tags_key)
+            .unwrap_or(Vec::new(&env));
+        let position = tags.first_index_of(&tag).ok_or(CertificateError::NotFound)?;
+        tags.remove(position);
+        if tags.is_empty() {
+            env.storage().instance().remove(&tags_key);
+        } else {
+            env.storage().instance().set(&tags_key, &tags);
+        }
+
+        let index_key = DataKey::Index(IndexKey::TagIndex(tag));
+        let mut ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(position) = ids.first_index_of(&id) {
+            ids.remove(position);
+        }
+        if ids.is_empty() {
+            env.storage().instance().remove(&index_key);
+        } else {
+            env.storage().instance().set(&index_key, &ids);
+        }
+
+        Ok(())
+    }
+
+    /// Get the tags attached to a certificate
+    pub fn get_tags(env: Env, id: String) -> Vec<String> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::CertificateTags(id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the IDs of every certificate carrying a tag, in tagging order
+    pub fn get_certificates_by_tag(env: Env, tag: String) -> Vec<String> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::TagIndex(tag)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Register a display alias for an owner. Purely informational, it has no
+    /// effect on certificate logic.
+    pub fn set_owner_alias(env: Env, owner: Address, alias: String) -> Result<(), CertificateError> {
+        owner.require_auth();
+
+        if alias.len() == 0 || alias.len() > MAX_ALIAS_LENGTH {
+            return Err(CertificateError::InvalidData);
+        }
+        env.storage().instance().set(&DataKey::OwnerAlias(owner), &alias);
+        Ok(())
+    }
+
+    /// Get the display alias registered by an owner
+    pub fn get_owner_alias(env: Env, owner: Address) -> Option<String> {
+        env.storage().instance().get(&DataKey::OwnerAlias(owner))
+    }
+
+    /// Look up a certificate ID by the content hash recorded at issuance
+    pub fn get_id_by_hash(env: Env, hash: BytesN<32>) -> Option<String> {
+        env.storage().instance().get(&DataKey::Index(IndexKey::HashToId(hash)))
+    }
+
+    /// Revoke a certificate identified only by its metadata content hash
+    pub fn revoke_by_hash(
+        env: Env,
+        hash: BytesN<32>,
+        reason: String,
+    ) -> Result<String, CertificateError> {
+        let id: String = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::HashToId(hash)))
+            .ok_or(CertificateError::NotFound)?;
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        let issuer = cert.issuer.clone();
+        Self::mark_revoked(&env, &mut cert, reason, issuer);
+        Self::save_certificate(&env, &mut cert);
+
+        Ok(id)
+    }
+
+    /// Update the metadata URI of many certificates in one call. Intended for
+    /// gateway migrations, so recorded metadata hashes are left unchanged.
+    ///
+    /// Every certificate must belong to `issuer`; if any does not, the whole
+    /// batch fails and nothing is written.
+    ///
+    /// # Arguments
+    /// * `issuer` - Issuer of every certificate in the batch
+    /// * `updates` - (certificate ID, new metadata URI) pairs
+    pub fn batch_update_metadata(
+        env: Env,
+        issuer: Address,
+        updates: Vec<(String, String)>,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+
+        if updates.len() > MAX_BATCH_SIZE {
+            return Err(CertificateError::InvalidData);
+        }
+
+        // Validate the whole batch before writing anything
+        let mut certs: Vec<Certificate> = Vec::new(&env);
+        for (id, new_uri) in updates.iter() {
+            let mut cert: Certificate = env
+                .storage()
+                .instance()
+                .get(&id)
+                .ok_or(CertificateError::NotFound)?;
+            if cert.issuer != issuer {
+                return Err(CertificateError::Unauthorized);
+            }
+            if new_uri.len() == 0 {
+                return Err(CertificateError::InvalidData);
+            }
+            Self::ensure_uri_allowed(&env, &issuer, &new_uri)?;
+            cert.metadata_uri = new_uri;
+            certs.push_back(cert);
+        }
+
+        for mut cert in certs.iter() {
+            Self::save_certificate(&env, &mut cert);
+        }
+
+        Ok(())
+    }
+
+    /// Return ownership of an expired certificate to its issuer
+    ///
+    /// Fails with `InvalidData` if the certificate has not expired.
+    pub fn reclaim_expired(env: Env, id: String) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if !Self::is_expired(&env, &cert) {
+            return Err(CertificateError::InvalidData);
+        }
+
+        // Do not pull the certificate out from under an in-flight transfer
+        if env.storage().instance().has(&DataKey::Transfer(TransferKey::ActiveTransfer(id.clone()))) {
+            return Err(CertificateError::TransferAlreadyActive);
+        }
+
+        let previous_owner = cert.owner.clone();
+        cert.owner = cert.issuer.clone();
+        Self::save_certificate(&env, &mut cert);
+        Self::move_owner_index(&env, &id, &previous_owner, &cert.issuer);
+        Self::emit_ownership_changed(
+            &env,
+            &id,
+            previous_owner.clone(),
+            Some(cert.issuer.clone()),
+            OwnershipChangeCause::Reclaim,
+        );
+
+        env.events().publish(
+            (symbol_short!("reclaim"), id.clone()),
+            CertificateReclaimedEvent {
+                certificate_id: id,
+                previous_owner,
+                issuer: cert.issuer,
+                reclaimed_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Emergency revocation of an issuer's certificates, e.g. after a key compromise
+    ///
+    /// Walks the issuer's certificates from offset `start` and revokes up to
+    /// `limit` that are not already revoked. Call repeatedly with increasing
+    /// offsets to stay under resource limits.
+    ///
+    /// # Returns
+    /// * `u32` - Number of certificates revoked by this call
+    pub fn revoke_all_by_issuer(
+        env: Env,
+        issuer: Address,
+        reason: String,
+        start: u32,
+        limit: u32,
+    ) -> u32 {
+        issuer.require_auth();
+
+        let ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::IssuerCertificates(issuer.clone())))
+            .unwrap_or(Vec::new(&env));
+
+        let mut revoked: u32 = 0;
+        for i in start..ids.len() {
+            if revoked >= limit {
+                break;
+            }
+            let id = ids.get(i).unwrap();
+            if let Some(mut cert) = env.storage().instance().get::<String, Certificate>(&id) {
+                if cert.is_revoked() {
+                    continue;
+                }
+                Self::mark_revoked(&env, &mut cert, reason.clone(), issuer.clone());
+                Self::save_certificate(&env, &mut cert);
+                revoked += 1;
+            }
+        }
+        revoked
+    }
+
+    /// Hand control of a certificate to a new issuing authority
+    ///
+    /// Revocation and other issuer rights follow the new issuer.
+    ///
+    /// # Arguments
+    /// * `id` - Certificate ID
+    /// * `new_issuer` - Address taking over as issuer
+    pub fn transfer_issuer(
+        env: Env,
+        id: String,
+        new_issuer: Address,
+    ) -> Result<(), CertificateError> {
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if cert.issuer == new_issuer {
+            return Err(CertificateError::InvalidData);
+        }
+
+        let old_issuer = cert.issuer.clone();
+        cert.issuer = new_issuer.clone();
+        Self::save_certificate(&env, &mut cert);
+        Self::remove_from_issuer_index(&env, &old_issuer, &id);
+        Self::add_to_issuer_index(&env, &new_issuer, &id);
+
+        env.events().publish(
+            (symbol_short!("iss_xfer"),),
+            IssuerTransferredEvent {
+                certificate_id: id,
+                old_issuer,
+                new_issuer,
+                transferred_at: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Allow another address to revoke certificates on the issuer's behalf
+    pub fn add_revocation_delegate(
+        env: Env,
+        issuer: Address,
+        delegate: Address,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+
+        let key = DataKey::Revocation(RevocationKey::RevocationDelegates(issuer));
+        let mut delegates: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if delegates.contains(&delegate) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        delegates.push_back(delegate);
+        env.storage().instance().set(&key, &delegates);
+        Ok(())
+    }
+
+    /// Withdraw a revocation delegate
+    pub fn remove_revocation_delegate(
+        env: Env,
+        issuer: Address,
+        delegate: Address,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+
+        let key = DataKey::Revocation(RevocationKey::RevocationDelegates(issuer));
+        let delegates: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let index = delegates
+            .first_index_of(&delegate)
+            .ok_or(CertificateError::NotFound)?;
+        let mut delegates = delegates;
+        delegates.remove(index);
+        env.storage().instance().set(&key, &delegates);
+        Ok(())
+    }
+
+    /// Get the revocation delegates registered by an issuer
+    pub fn get_revocation_delegates(env: Env, issuer: Address) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Revocation(RevocationKey::RevocationDelegates(issuer)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Revoke a certificate as one of its issuer's registered delegates
+    pub fn delegate_revoke_certificate(
+        env: Env,
+        id: String,
+        delegate: Address,
+        reason: String,
+    ) -> Result<(), CertificateError> {
+        delegate.require_auth();
+
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        if !Self::get_revocation_delegates(env.clone(), cert.issuer.clone()).contains(&delegate) {
+            return Err(CertificateError::Unauthorized);
+        }
+        if cert.is_revoked() {
+            return Err(CertificateError::AlreadyRevoked);
+        }
+
+        Self::mark_revoked(&env, &mut cert, reason, delegate);
+        Self::save_certificate(&env, &mut cert);
+        Ok(())
+    }
+
+    /// Check whether an address may revoke a certificate, either as its issuer
+    /// or as a registered revocation delegate
+    pub fn can_revoke(env: Env, id: String, address: Address) -> Result<bool, CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+        if cert.issuer == address {
+            return Ok(true);
+        }
+        Ok(Self::get_revocation_delegates(env, cert.issuer).contains(&address))
+    }
+
+    /// Renounce a certificate so the owner no longer holds it
+    ///
+    /// The owner field is left untouched for auditability, but the certificate
+    /// is flagged as renounced and can no longer be transferred. The issuer can
+    /// still revoke a renounced certificate.
+    ///
+    /// # Arguments
+    /// * `id` - Certificate ID to renounce
+    /// * `owner` - Current owner disclaiming the certificate
+    pub fn renounce_certificate(
+        env: Env,
+        id: String,
+        owner: Address,
+    ) -> Result<(), CertificateError> {
+        owner.require_auth();
+
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        if cert.owner != owner {
+            return Err(CertificateError::Unauthorized);
+        }
+
+        if cert.is_renounced() {
+            return Err(CertificateError::AlreadyRenounced);
+        }
+
+        let renounced_at = env.ledger().timestamp();
+        cert.set_flag(FLAG_RENOUNCED, true);
+        Self::save_certificate(&env, &mut cert);
+        Self::emit_ownership_changed(&env, &id, owner.clone(), None, OwnershipChangeCause::Renounce);
+
+        env.events().publish(
+            (symbol_short!("renounce"),),
+            CertificateRenouncedEvent {
+                certificate_id: id,
+                owner,
+                renounced_at,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Check if a certificate has been renounced by its owner
+    pub fn is_renounced(env: Env, id: String) -> bool {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .expect("Certificate not found");
+        cert.is_renounced()
+    }
+
+    /// Register the Ed25519 public key an issuer uses to sign off-chain issuances
+    pub fn register_issuer_key(
+        env: Env,
+        issuer: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), CertificateError> {
+        issuer.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::IssuerPublicKey(issuer), &public_key);
+        Ok(())
+    }
+
+    /// Get the Ed25519 public key registered for an issuer
+    pub fn get_issuer_key(env: Env, issuer: Address) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::IssuerPublicKey(issuer))
+    }
+
+    /// Claim a certificate the issuer signed off-chain, paying the issuance cost
+    /// as the recipient
+    ///
+    /// The signed message is the XDR encoding of
+    /// `(contract address, id, issuer, owner, metadata_uri, issued_at)`.
+    /// Signatures are accepted for seven days after `issued_at`.
+    ///
+    /// # Errors
+    /// * `InvalidSignature` - The issuer has no registered key, `issued_at`
+    ///   is in the future or older than the validity window, or the signature
+    ///   does not verify
+    /// * `InvalidData` - The encoded payload exceeds `MAX_CLAIM_PAYLOAD_LENGTH`
+    ///
+    /// # Arguments
+    /// * `signature` - Issuer's Ed25519 signature over the payload
+    /// * `issued_at` - Time the issuer signed the payload
+    pub fn claim_certificate(
+        env: Env,
+        id: String,
+        issuer: Address,
+        owner: Address,
+        metadata_uri: String,
+        signature: BytesN<64>,
+        issued_at: u64,
+    ) -> Result<(), CertificateError> {
+        owner.require_auth();
+
+        let public_key: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::IssuerPublicKey(issuer.clone()))
+            .ok_or(CertificateError::InvalidSignature)?;
+
+        // Signatures must be current: not from the future, not stale
+        let now = env.ledger().timestamp();
+        if issued_at > now || now - issued_at > CLAIM_SIGNATURE_VALIDITY_SECONDS {
+            return Err(CertificateError::InvalidSignature);
+        }
+
+        let payload = (
+            env.current_contract_address(),
+            id.clone(),
+            issuer.clone(),
+            owner.clone(),
+            metadata_uri.clone(),
+            issued_at,
+        )
+            .to_xdr(&env);
+        Self::verify_claim_signature(&public_key, &payload, &signature)?;
+
+        Self::store_new_certificate(&env, id, issuer, owner, metadata_uri, None, None, false, Vec::new(&env))
+    }
+
+    /// Freeze a certificate temporarily during a dispute
+    /// 
+    /// # Arguments
+    /// * `id` - Certificate ID to freeze
+    /// * `admin` - Admin address that has authority to freeze
+    /// * `reason` - Reason for freezing the certificate
+    /// * `duration_days` - Number of days to freeze (0 for permanent freeze, max 90 days)
+    /// 
+    /// # Returns
+    /// * `CertificateFrozenEvent` - Event emitted when certificate is frozen
+    pub fn freeze_certificate(
+        env: Env,
+        id: String,
+        admin: Address,
+        reason: String,
+        duration_days: u32,
+    ) -> CertificateFrozenEvent {
+        admin.require_auth();
+
+        let mut cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .expect("Certificate not found");
+
+        // Check if already frozen
+        if cert.is_frozen() {
+            panic!("Certificate is already frozen");
+        }
+
+        // Check if certificate is revoked
+        if cert.is_revoked() {
+            panic!("Cannot freeze a revoked certificate");
+        }
+
+        // Validate duration
+        if duration_days > 90 {
+            panic!("Freeze duration cannot exceed 90 days");
+        }
+
         let current_time = env.ledger().timestamp();
         let unfreeze_at = if duration_days > 0 {
             // Calculate unfreeze time (duration_days * 24 * 60 * 60 seconds)
@@ -732,10 +4237,10 @@ impl CertificateContract {
         };
 
         // Update certificate
-        cert.frozen = true;
+        cert.set_flag(FLAG_FROZEN, true);
         cert.freeze_info = Some(freeze_info.clone());
 
-        env.storage().instance().set(&id, &cert);
+        Self::save_certificate(&env, &mut cert);
 
         // Store freeze info in separate key for history
         let freeze_key = DataKey::FrozenCertificate(id.clone());
@@ -783,7 +4288,7 @@ impl CertificateContract {
             .expect("Certificate not found");
 
         // Check if frozen
-        if !cert.frozen {
+        if !cert.is_frozen() {
             panic!("Certificate is not frozen");
         }
 
@@ -791,10 +4296,10 @@ impl CertificateContract {
         let was_auto_unfreeze = false;
 
         // Update certificate
-        cert.frozen = false;
+        cert.set_flag(FLAG_FROZEN, false);
         cert.freeze_info = None;
 
-        env.storage().instance().set(&id, &cert);
+        Self::save_certificate(&env, &mut cert);
 
         // Remove freeze info from storage
         let freeze_key = DataKey::FrozenCertificate(id.clone());
@@ -836,7 +4341,7 @@ impl CertificateContract {
             .instance()
             .get(&id)
             .expect("Certificate not found");
-        cert.frozen
+        cert.is_frozen()
     }
 
     /// Get freeze information for a certificate
@@ -866,7 +4371,7 @@ impl CertificateContract {
             .expect("Certificate not found");
 
         // Check if frozen
-        if !cert.frozen {
+        if !cert.is_frozen() {
             panic!("Certificate is not frozen");
         }
 
@@ -874,10 +4379,10 @@ impl CertificateContract {
         let was_auto_unfreeze = false;
 
         // Update certificate
-        cert.frozen = false;
+        cert.set_flag(FLAG_FROZEN, false);
         cert.freeze_info = None;
 
-        env.storage().instance().set(&id, &cert);
+        Self::save_certificate(&env, &mut cert);
 
         // Remove freeze info from storage
         let freeze_key = DataKey::FrozenCertificate(id.clone());
@@ -900,20 +4405,366 @@ impl CertificateContract {
         event
     }
 
+    /// Check if a certificate is revoked. An expired certificate issued with
+    /// `auto_revoke_on_expiry`, or one whose scheduled revocation has taken
+    /// effect, is reported as revoked without touching storage.
     pub fn is_revoked(env: Env, id: String) -> bool {
         let cert: Certificate = env
             .storage()
             .instance()
             .get(&id)
             .expect("Certificate not found");
-        cert.revoked
+        cert.is_revoked()
+            || Self::is_scheduled_revocation_due(&env, &cert)
+            || (cert.auto_revoke_on_expiry() && Self::is_expired(&env, &cert))
+    }
+
+    /// Verify a certificate's current status
+    ///
+    /// Expiry is interpreted at read time: an expired certificate issued with
+    /// `auto_revoke_on_expiry` verifies as `Revoked` with a synthetic
+    /// `RevocationReason::Expired`, otherwise as `Expired`. Storage is not modified.
+    pub fn verify_certificate(env: Env, id: String) -> Result<CertificateVerification, CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        let (status, revocation_reason) = if cert.is_revoked() {
+            (
+                CertificateStatus::Revoked,
+                cert.revocation_reason.map(RevocationReason::Other),
+            )
+        } else if Self::is_scheduled_revocation_due(&env, &cert) {
+            (
+                CertificateStatus::Revoked,
+                cert.scheduled_revocation_reason.map(RevocationReason::Other),
+            )
+        } else if cert.is_suspended() {
+            (CertificateStatus::Suspended, None)
+        } else if Self::is_expired(&env, &cert) {
+            if cert.auto_revoke_on_expiry() {
+                (CertificateStatus::Revoked, Some(RevocationReason::Expired))
+            } else {
+                (CertificateStatus::Expired, None)
+            }
+        } else {
+            (CertificateStatus::Valid, None)
+        };
+
+        Ok(CertificateVerification {
+            id,
+            status,
+            revocation_reason,
+            verified_at: env.ledger().timestamp(),
+        })
+    }
+
+    /// Restrict the recipients a certificate may be transferred to. An empty
+    /// list removes the restriction.
+    pub fn set_transfer_whitelist(
+        env: Env,
+        id: String,
+        recipients: Vec<Address>,
+    ) -> Result<(), CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        let key = DataKey::Transfer(TransferKey::TransferWhitelist(id));
+        if recipients.is_empty() {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &recipients);
+        }
+        Ok(())
+    }
+
+    /// Get the permitted recipients of a certificate (empty = unrestricted)
+    pub fn get_transfer_whitelist(env: Env, id: String) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferWhitelist(id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Record that a certificate builds on a prerequisite certificate
+    ///
+    /// # Arguments
+    /// * `id` - Certificate that depends on the prerequisite
+    /// * `prerequisite_id` - Certificate that must remain valid
+    pub fn link_certificate(
+        env: Env,
+        id: String,
+        prerequisite_id: String,
+    ) -> Result<(), CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        cert.issuer.require_auth();
+
+        if id == prerequisite_id {
+            return Err(CertificateError::InvalidData);
+        }
+        if !env.storage().instance().has(&prerequisite_id) {
+            return Err(CertificateError::NotFound);
+        }
+
+        let key = DataKey::Prerequisites(id);
+        let mut prerequisites: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        if !prerequisites.contains(&prerequisite_id) {
+            prerequisites.push_back(prerequisite_id);
+            env.storage().instance().set(&key, &prerequisites);
+        }
+
+        Ok(())
+    }
+
+    /// Get the prerequisite certificate IDs linked to a certificate
+    pub fn get_prerequisites(env: Env, id: String) -> Vec<String> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Prerequisites(id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check that a certificate and each of its direct prerequisites verify
+    /// as valid
+    pub fn verify_with_prerequisites(env: Env, id: String) -> bool {
+        let is_valid = |cert_id: String| {
+            matches!(
+                Self::verify_certificate(env.clone(), cert_id),
+                Ok(CertificateVerification { status: CertificateStatus::Valid, .. })
+            )
+        };
+
+        if !is_valid(id.clone()) {
+            return false;
+        }
+
+        Self::get_prerequisites(env.clone(), id)
+            .iter()
+            .all(|prerequisite_id| is_valid(prerequisite_id))
+    }
+
+    /// Page through revoked certificate IDs in revocation order, for building
+    /// off-chain revocation lists
+    pub fn get_revoked_certificates(env: Env, start: u32, limit: u32) -> Vec<String> {
+        let index: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::RevokedIndex))
+            .unwrap_or(Vec::new(&env));
+
+        let mut page = Vec::new(&env);
+        let end = start.saturating_add(limit).min(index.len());
+        for i in start..end {
+            page.push_back(index.get(i).unwrap());
+        }
+        page
+    }
+
+    /// Page through unrevoked certificates that expire before `timestamp`,
+    /// soonest first, for renewal reminders. `start` skips that many matches.
+    pub fn get_certificates_expiring_before(
+        env: Env,
+        timestamp: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<String> {
+        let index: Vec<(u64, String)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::ExpiryIndex))
+            .unwrap_or(Vec::new(&env));
+
+        let mut page = Vec::new(&env);
+        let mut skipped = 0u32;
+        for (expires_at, id) in index.iter() {
+            if expires_at >= timestamp || page.len() >= limit {
+                break;
+            }
+            let cert: Option<Certificate> = env.storage().instance().get(&id);
+            if !matches!(cert, Some(ref c) if !c.is_revoked()) {
+                continue;
+            }
+            if skipped < start {
+                skipped += 1;
+                continue;
+            }
+            page.push_back(id);
+        }
+        page
+    }
+
+    /// Get up to `limit` certificate IDs issued within `[from_ts, to_ts]`,
+    /// in issuance order
+    pub fn get_certificates_issued_between(
+        env: Env,
+        from_ts: u64,
+        to_ts: u64,
+        limit: u32,
+    ) -> Vec<String> {
+        let log: Vec<(String, u64)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::IssuanceLog))
+            .unwrap_or(Vec::new(&env));
+
+        let mut ids = Vec::new(&env);
+        for (id, issued_at) in log.iter() {
+            if ids.len() >= limit || issued_at > to_ts {
+                break;
+            }
+            if issued_at >= from_ts {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    /// Get the number of revoked certificates. A scheduled revocation counts
+    /// once it is applied with `apply_scheduled_revocation`.
+    pub fn get_revoked_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::RevokedCount))
+            .unwrap_or(0)
+    }
+
+    /// Get the number of issued certificates that are not revoked
+    pub fn get_active_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::ActiveCount))
+            .unwrap_or(0)
+    }
+
+    pub fn get_certificate(env: Env, id: String) -> CertificateView {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .expect("Certificate not found");
+        cert.into()
+    }
+
+    /// Get a certificate if it exists, without failing on unknown ids
+    pub fn get_certificate_opt(env: Env, id: String) -> Option<Certificate> {
+        env.storage().instance().get(&id)
+    }
+
+    /// Get the IDs of all certificates currently owned by an address
+    pub fn get_certificates_by_owner(env: Env, owner: Address) -> Vec<String> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::OwnerCertificates(owner)))
+            .unwrap_or(Vec::new(&env))
     }
 
-    pub fn get_certificate(env: Env, id: String) -> Certificate {
+    /// Get the IDs of all certificates controlled by an issuer
+    pub fn get_certificates_by_issuer(env: Env, issuer: Address) -> Vec<String> {
         env.storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::IssuerCertificates(issuer)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the IDs of an owner's certificates that are still usable, skipping
+    /// revoked, renounced and expired ones
+    pub fn get_active_certificates_by_owner(env: Env, owner: Address) -> Vec<String> {
+        let ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::OwnerCertificates(owner)))
+            .unwrap_or(Vec::new(&env));
+
+        let mut active = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(cert) = env.storage().instance().get::<String, Certificate>(&id) {
+                if !cert.is_revoked() && !cert.is_renounced() && !Self::is_expired(&env, &cert) {
+                    active.push_back(id);
+                }
+            }
+        }
+        active
+    }
+
+    /// Check whether an owner currently holds any valid certificate from an
+    /// issuer. Revoked, suspended, renounced and expired certificates do not count.
+    pub fn has_valid_certificate_from(env: Env, owner: Address, issuer: Address) -> bool {
+        let ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::OwnerCertificates(owner)))
+            .unwrap_or(Vec::new(&env));
+
+        for id in ids.iter() {
+            if let Some(cert) = env.storage().instance().get::<String, Certificate>(&id) {
+                if cert.issuer == issuer
+                    && !cert.is_revoked()
+                    && !cert.is_suspended()
+                    && !cert.is_renounced()
+                    && !Self::is_expired(&env, &cert)
+                    && !Self::is_scheduled_revocation_due(&env, &cert)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Get the owner of a certificate at a past timestamp, using its
+    /// transfer history. A transfer at exactly `timestamp` counts as done.
+    pub fn owner_at(env: Env, id: String, timestamp: u64) -> Result<Address, CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        if timestamp < cert.issued_at {
+            return Err(CertificateError::InvalidData);
+        }
+
+        // The first change after the timestamp was made by the owner at that time
+        let history: Vec<TransferHistory> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferHistory(id)))
+            .unwrap_or(Vec::new(&env));
+        for entry in history.iter() {
+            if entry.transferred_at > timestamp {
+                return Ok(entry.from_address);
+            }
+        }
+
+        Ok(cert.owner)
+    }
+
+    /// Check whether an address currently owns a certificate.
+    /// A renounced certificate has no current owner.
+    pub fn is_owner(env: Env, id: String, address: Address) -> Result<bool, CertificateError> {
+        let cert: Certificate = env
+            .storage()
             .instance()
             .get(&id)
-            .expect("Certificate not found")
+            .ok_or(CertificateError::NotFound)?;
+        Ok(cert.owner == address && !cert.is_renounced())
     }
 
     pub fn batch_verify_certificates(env: Env, ids: Vec<String>) -> BatchVerificationResult {
@@ -959,7 +4810,7 @@ impl CertificateContract {
                 .instance()
                 .get(&id)
                 .expect("Certificate should exist");
-            let revoked = cert.revoked;
+            let revoked = cert.is_revoked();
 
             if revoked {
                 let result = SingleVerificationResult {
@@ -1077,7 +4928,7 @@ impl CertificateContract {
             .ok_or(CertificateError::NotFound)?;
         
         // Check if certificate is revoked
-        if certificate.revoked {
+        if certificate.is_revoked() {
             return Err(CertificateError::AlreadyRevoked);
         }
         
@@ -1190,7 +5041,8 @@ impl CertificateContract {
         Ok(())
     }
 
-    // Initiates a certificate transfer
+    // Initiates a certificate transfer with a `u64` fee. Kept for existing
+    // callers; delegates to `initiate_transfer_v2` with the fee widened.
     pub fn initiate_transfer(
         env: Env,
         transfer_id: String,
@@ -1200,76 +5052,209 @@ impl CertificateContract {
         require_revocation: bool,
         transfer_fee: u64,
         memo: Option<String>,
+        notify: bool,
+        completer_policy: Option<CompleterPolicy>,
+    ) -> Result<(), CertificateError> {
+        Self::initiate_transfer_v2(
+            env,
+            transfer_id,
+            certificate_id,
+            from_address,
+            to_address,
+            require_revocation,
+            transfer_fee as i128,
+            memo,
+            notify,
+            completer_policy,
+            None,
+        )
+    }
+
+    // Initiates a certificate transfer with an `i128` fee matching SEP-41
+    // token amounts, optionally fixing extra terms such as a completion
+    // deadline
+    pub fn initiate_transfer_v2(
+        env: Env,
+        transfer_id: String,
+        certificate_id: String,
+        from_address: Address,
+        to_address: Address,
+        require_revocation: bool,
+        transfer_fee: i128,
+        memo: Option<String>,
+        notify: bool,
+        completer_policy: Option<CompleterPolicy>,
+        options: Option<TransferOptions>,
     ) -> Result<(), CertificateError> {
         // Authenticate the current owner
         from_address.require_auth();
         
-        // Check if transfer already exists
-        let transfer_key = DataKey::TransferRequest(transfer_id.clone());
-        if env.storage().instance().has(&transfer_key) {
-            return Err(CertificateError::AlreadyExists);
+        // Reject malformed identifiers
+        if transfer_id.len() == 0 || certificate_id.len() == 0 {
+            return Err(CertificateError::InvalidData);
         }
         
-        // Get the certificate
-        let mut cert: Certificate = env.storage().instance().get(&certificate_id).ok_or(CertificateError::NotFound)?;
+        // Fees are token amounts and cannot be negative
+        if transfer_fee < 0 {
+            return Err(CertificateError::InvalidData);
+        }
         
-        // Verify the sender is the current owner
-        if cert.owner != from_address {
-            return Err(CertificateError::Unauthorized);
+        // Bound the memo so it cannot bloat storage and events
+        if let Some(ref m) = memo {
+            if m.len() > MAX_MEMO_LENGTH {
+                return Err(CertificateError::InvalidData);
+            }
         }
         
-        // Check if certificate is revoked
-        if cert.revoked {
-            return Err(CertificateError::AlreadyRevoked);
+        // Keep the fee within the configured floor and cap
+        let (min_fee, max_fee) = Self::get_transfer_fee_limits(env.clone());
+        if transfer_fee < min_fee || (max_fee != 0 && transfer_fee > max_fee) {
+            return Err(CertificateError::InvalidData);
         }
         
-        // Check if recipient is different from sender
-        if from_address == to_address {
+        // Fee-bearing transfers may be required to explain themselves
+        if transfer_fee > 0 && memo.is_none() && Self::is_memo_required_on_fee(env.clone()) {
             return Err(CertificateError::InvalidData);
         }
         
+        // A completion deadline must lie in the future
+        let options = options.unwrap_or(TransferOptions { complete_by: None, ttl_seconds: 0, data: None, prepay_fee: false });
+        if let Some(complete_by) = options.complete_by {
+            if complete_by <= env.ledger().timestamp() {
+                return Err(CertificateError::InvalidData);
+            }
+        }
+        
+        // Bound the payload so it cannot bloat storage
+        if let Some(ref data) = options.data {
+            if data.len() > MAX_TRANSFER_DATA_LENGTH {
+                return Err(CertificateError::InvalidData);
+            }
+        }
+        
+        // Prepaying needs a fee to escrow and a token to escrow it in
+        let prepaid_token = if options.prepay_fee {
+            let config: FeeConfig = env
+                .storage()
+                .instance()
+                .get(&DataKey::Config(ConfigKey::FeeConfig))
+                .ok_or(CertificateError::InvalidData)?;
+            if transfer_fee == 0 {
+                return Err(CertificateError::InvalidData);
+            }
+            Some(config.token)
+        } else {
+            None
+        };
+        
+        // Check if transfer already exists, or existed and was purged
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let used_key = DataKey::Transfer(TransferKey::UsedTransferId(transfer_id.clone()));
+        if env.storage().instance().has(&transfer_key) || env.storage().instance().has(&used_key) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        
+        // Validate ownership, certificate state and recipient
+        Self::validate_transfer(&env, &certificate_id, &from_address, &to_address)?;
+        
+        // Enforce the per-owner transfer rate limit
+        Self::consume_transfer_allowance(&env, &from_address)?;
+        
+        // Recipients can pre-approve transfers from a given sender
+        let preapproved: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::Preapproval(to_address.clone(), from_address.clone())))
+            .unwrap_or(false);
+        let now = env.ledger().timestamp();
+        
         // Create transfer request
         let transfer = TransferRequest {
             id: transfer_id.clone(),
             certificate_id: certificate_id.clone(),
             from_address: from_address.clone(),
             to_address: to_address.clone(),
-            initiated_at: env.ledger().timestamp(),
-            accepted_at: None,
+            initiated_at: now,
+            accepted_at: if preapproved { Some(now) } else { None },
             completed_at: None,
-            status: TransferStatus::Pending,
+            status: if preapproved { TransferStatus::Accepted } else { TransferStatus::Pending },
             require_revocation,
             transfer_fee,
             memo,
+            notify_recipient: notify,
+            complete_by: options.complete_by,
+            completer_policy: completer_policy.unwrap_or(CompleterPolicy::AnyParty),
+            expires_at: if options.ttl_seconds == 0 {
+                Self::default_transfer_expiry(&env, now)
+            } else {
+                Some(now.saturating_add(options.ttl_seconds))
+            },
+            data: options.data,
+            prepaid_token: prepaid_token.clone(),
         };
         
-        // Store the transfer request
+        // Store the transfer request and reserve its id for good
         env.storage().instance().set(&transfer_key, &transfer);
+        Self::count_transfer_status(&env, None, &transfer.status);
+        env.storage().instance().set(&used_key, &true);
         
-        // Add to recipient's pending transfers
-        let pending_key = DataKey::PendingTransfers(to_address.clone());
-        let mut pending_transfers: Vec<String> = env
+        // Index the transfer under its certificate, whatever its outcome
+        let cert_transfers_key = DataKey::Transfer(TransferKey::CertTransfers(certificate_id.clone()));
+        let mut cert_transfers: Vec<String> = env
             .storage()
             .instance()
-            .get(&pending_key)
+            .get(&cert_transfers_key)
             .unwrap_or(Vec::new(&env));
-        pending_transfers.push_back(transfer_id.clone());
-        env.storage().instance().set(&pending_key, &pending_transfers);
+        cert_transfers.push_back(transfer_id.clone());
+        env.storage().instance().set(&cert_transfers_key, &cert_transfers);
+        
+        // Mark the certificate as having an active transfer
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::ActiveTransfer(certificate_id.clone())), &transfer_id);
+        
+        // Add to recipient's pending transfers, unless already accepted
+        if !preapproved {
+            let pending_key = DataKey::Transfer(TransferKey::PendingTransfers(to_address.clone()));
+            let mut pending_transfers: Vec<String> = env
+                .storage()
+                .instance()
+                .get(&pending_key)
+                .unwrap_or(Vec::new(&env));
+            pending_transfers.push_back(transfer_id.clone());
+            env.storage().instance().set(&pending_key, &pending_transfers);
+
+            let total: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::PendingTransferTotal))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::Transfer(TransferKey::PendingTransferTotal), &(total + 1));
+        }
         
         // Update transfer count
-        let count: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TransferCount)
-            .unwrap_or(0);
-        env.storage().instance().set(&DataKey::TransferCount, &(count + 1));
+        Self::increment_transfer_count(&env)?;
+        
+        // Escrow the fee once the transfer is recorded, under the lock
+        if let Some(ref token_address) = prepaid_token {
+            Self::acquire_lock(&env)?;
+            token::Client::new(&env, token_address).transfer(
+                &from_address,
+                &env.current_contract_address(),
+                &transfer_fee,
+            );
+            Self::release_lock(&env);
+        }
         
         // Emit transfer initiated event
-        env.events().publish(
-            (symbol_short!("transfer_init"),),
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_init"), certificate_id.clone()),
             TransferInitiatedEvent {
                 transfer_id: transfer_id.clone(),
-                certificate_id,
+                certificate_id: certificate_id.clone(),
                 from_address,
                 to_address,
                 initiated_at: transfer.initiated_at,
@@ -1277,9 +5262,106 @@ impl CertificateContract {
             },
         );
         
+        // Pre-approved transfers are accepted on the recipient's behalf
+        if preapproved {
+            Self::publish_transfer_event(
+                &env,
+                (symbol_short!("transfer_accept"), certificate_id),
+                TransferAcceptedEvent {
+                    transfer_id,
+                    accepted_at: now,
+                },
+            );
+        }
+        
+        Ok(())
+    }
+
+    // Transfers a certificate held by its issuer straight to the recipient.
+    // Issuance implies the recipient's consent, so the transfer is accepted
+    // and completed in the same call. Requires the issuer auto-transfer flag.
+    pub fn initiate_issuer_transfer(
+        env: Env,
+        transfer_id: String,
+        certificate_id: String,
+        issuer: Address,
+        to_address: Address,
+        memo: Option<String>,
+    ) -> Result<(), CertificateError> {
+        if !Self::is_issuer_auto_transfer_enabled(env.clone()) {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        if cert.issuer != issuer {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        Self::initiate_transfer_v2(
+            env.clone(),
+            transfer_id.clone(),
+            certificate_id,
+            issuer,
+            to_address,
+            false,
+            0,
+            memo,
+            false,
+            None,
+            None,
+        )?;
+        
+        let mut transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id)))
+            .ok_or(CertificateError::TransferNotFound)?;
+        if transfer.status == TransferStatus::Pending {
+            Self::record_acceptance(&env, &mut transfer);
+        }
+        
+        Self::finalize_transfer(&env, transfer, cert)
+    }
+
+    // Pre-approves incoming transfers from a sender so they are accepted as
+    // soon as they are initiated. Completion is still a separate step.
+    pub fn preapprove_incoming(
+        env: Env,
+        recipient: Address,
+        from_address: Address,
+    ) -> Result<(), CertificateError> {
+        recipient.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::Preapproval(recipient, from_address)), &true);
+        Ok(())
+    }
+
+    // Withdraws a pre-approval for incoming transfers from a sender
+    pub fn revoke_preapproval(
+        env: Env,
+        recipient: Address,
+        from_address: Address,
+    ) -> Result<(), CertificateError> {
+        recipient.require_auth();
+        env.storage()
+            .instance()
+            .remove(&DataKey::Transfer(TransferKey::Preapproval(recipient, from_address)));
         Ok(())
     }
 
+    // Check whether a recipient has pre-approved transfers from a sender
+    pub fn is_preapproved(env: Env, recipient: Address, from_address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::Preapproval(recipient, from_address)))
+            .unwrap_or(false)
+    }
+
     // Approve a certificate upgrade
     pub fn approve_upgrade(
         env: Env,
@@ -1415,7 +5497,7 @@ impl CertificateContract {
             owner: certificate.owner.clone(),
             metadata_uri: certificate.metadata_uri.clone(),
             issued_at: env.ledger().timestamp(),
-            revoked: false,
+            flags: certificate.flags & FLAG_AUTO_REVOKE_ON_EXPIRY,
             revocation_reason: None,
             revoked_at: None,
             revoked_by: None,
@@ -1425,9 +5507,24 @@ impl CertificateContract {
             is_upgradable: certificate.is_upgradable,
             upgrade_rules: certificate.upgrade_rules.clone(),
             compatibility_matrix: certificate.compatibility_matrix.clone(),
+            freeze_info: None,
+            expires_at: certificate.expires_at,
+            suspension_reason: None,
+            nonce: 0,
+            metadata_hash: certificate.metadata_hash.clone(),
+            last_modified_at: env.ledger().timestamp(),
+            scheduled_revocation_at: certificate.scheduled_revocation_at,
+            scheduled_revocation_reason: certificate.scheduled_revocation_reason.clone(),
+            supersedes: certificate.supersedes.clone(),
+            superseded_by: None,
+            backup_uris: certificate.backup_uris.clone(),
+            max_transfers: certificate.max_transfers,
+            transfer_count: 0,
+            verifier: certificate.verifier.clone(),
         };
         
         // Store new certificate
+        Self::advance_certificate_index(&env)?;
         let new_cert_key = DataKey::Certificate(new_certificate_id.clone());
         env.storage().instance().set(&new_cert_key, &new_certificate);
         
@@ -1467,11 +5564,321 @@ impl CertificateContract {
             },
         );
         
-        Ok(new_certificate_id)
+        Ok(new_certificate_id)
+    }
+
+    // Accepts a certificate transfer
+    pub fn accept_transfer(
+        env: Env,
+        transfer_id: String,
+        recipient: Address,
+    ) -> Result<(), CertificateError> {
+        // Authenticate the recipient
+        recipient.require_auth();
+        
+        let mut transfer = Self::check_acceptance(&env, &transfer_id, &recipient)?;
+        Self::record_acceptance(&env, &mut transfer);
+        
+        Ok(())
+    }
+
+    /// Check whether `recipient` could accept a transfer right now, without
+    /// requiring auth or changing any state. Returns the error
+    /// `accept_transfer` would fail with.
+    pub fn can_accept(env: Env, transfer_id: String, recipient: Address) -> Result<(), CertificateError> {
+        Self::check_acceptance(&env, &transfer_id, &recipient)?;
+        Ok(())
+    }
+
+    // Accepts a pending transfer and completes it in the same call. The
+    // recipient must be an allowed completer under the transfer's policy.
+    pub fn accept_and_complete(
+        env: Env,
+        transfer_id: String,
+        recipient: Address,
+    ) -> Result<(), CertificateError> {
+        // Authenticate the recipient
+        recipient.require_auth();
+        
+        // Get the transfer request
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id));
+        let mut transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        // Verify the recipient is the intended recipient
+        if transfer.to_address != recipient {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        // Check if transfer is still pending
+        if transfer.status != TransferStatus::Pending {
+            return Err(CertificateError::TransferNotPending);
+        }
+        
+        // Pending transfers lapse once their TTL runs out
+        if Self::is_transfer_expired(&env, &transfer) {
+            return Err(CertificateError::TransferExpired);
+        }
+        
+        // Time-boxed transfers cannot complete after their deadline
+        if let Some(complete_by) = transfer.complete_by {
+            if env.ledger().timestamp() > complete_by {
+                return Err(CertificateError::TransferDeadlinePassed);
+            }
+        }
+        
+        // Get the certificate
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&transfer.certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        
+        // The recipient must also be allowed to complete
+        if !Self::is_allowed_completer(&transfer, &cert, &recipient) {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        Self::record_acceptance(&env, &mut transfer);
+        Self::finalize_transfer(&env, transfer, cert)
+    }
+
+    // Completes a certificate transfer (called after acceptance). If the transfer
+    // was initiated with `notify`, a contract recipient's receive hook is called
+    // and the transfer reverts if the hook fails or declines.
+    pub fn complete_transfer(
+        env: Env,
+        transfer_id: String,
+        executor: Address,
+    ) -> Result<(), CertificateError> {
+        // Authenticate the executor (can be sender, recipient, or admin)
+        executor.require_auth();
+        
+        // Get the transfer request
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        // Check if transfer is accepted
+        if transfer.status != TransferStatus::Accepted {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        
+        // Time-boxed transfers cannot complete after their deadline
+        if let Some(complete_by) = transfer.complete_by {
+            if env.ledger().timestamp() > complete_by {
+                return Err(CertificateError::TransferDeadlinePassed);
+            }
+        }
+        
+        // Get the certificate
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&transfer.certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        
+        // Verify authorization against the transfer's completer policy
+        if !Self::is_allowed_completer(&transfer, &cert, &executor) {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        Self::finalize_transfer(&env, transfer, cert)
+    }
+
+    // Requires `threshold` of `approvers` to sign off on every transfer of a
+    // certificate before it can complete, for certificates held by an
+    // organization. An empty approver list removes the requirement.
+    pub fn set_transfer_approvers(
+        env: Env,
+        certificate_id: String,
+        approvers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), CertificateError> {
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        
+        // Authenticate the owner
+        cert.owner.require_auth();
+        
+        // The quorum cannot change under an in-flight transfer
+        if env.storage().instance().has(&DataKey::Transfer(TransferKey::ActiveTransfer(certificate_id.clone()))) {
+            return Err(CertificateError::TransferAlreadyActive);
+        }
+        
+        let key = DataKey::Transfer(TransferKey::TransferApprovers(certificate_id));
+        if approvers.is_empty() {
+            env.storage().instance().remove(&key);
+            return Ok(());
+        }
+        if threshold == 0 || threshold > approvers.len() {
+            return Err(CertificateError::InvalidData);
+        }
+        env.storage().instance().set(&key, &TransferApprovalConfig { approvers, threshold });
+        
+        Ok(())
+    }
+
+    // Get the approval quorum required to transfer a certificate, if any
+    pub fn get_transfer_approvers(env: Env, certificate_id: String) -> Option<TransferApprovalConfig> {
+        env.storage().instance().get(&DataKey::Transfer(TransferKey::TransferApprovers(certificate_id)))
+    }
+
+    // Records an approver's sign-off on an in-flight transfer or open offer.
+    // Returns the number of approvals gathered so far.
+    pub fn approve_pending_transfer(
+        env: Env,
+        transfer_id: String,
+        approver: Address,
+    ) -> Result<u32, CertificateError> {
+        // Authenticate the approver
+        approver.require_auth();
+        
+        let transfer: Option<TransferRequest> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())));
+        let (certificate_id, status) = match transfer {
+            Some(transfer) => (transfer.certificate_id, transfer.status),
+            None => {
+                let offer: OpenOffer = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::Transfer(TransferKey::OpenOffer(transfer_id.clone())))
+                    .ok_or(CertificateError::TransferNotFound)?;
+                (offer.certificate_id, offer.status)
+            }
+        };
+        if status != TransferStatus::Pending && status != TransferStatus::Accepted {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        
+        let config: TransferApprovalConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferApprovers(certificate_id)))
+            .ok_or(CertificateError::NotFound)?;
+        if !config.approvers.contains(&approver) {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        let approvals_key = DataKey::Transfer(TransferKey::TransferApprovals(transfer_id));
+        let mut approvals: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&approvals_key)
+            .unwrap_or(Vec::new(&env));
+        if approvals.contains(&approver) {
+            return Err(CertificateError::AlreadyExists);
+        }
+        approvals.push_back(approver);
+        env.storage().instance().set(&approvals_key, &approvals);
+        
+        Ok(approvals.len())
+    }
+
+    // Closes a pending transfer whose acceptance deadline has passed. Anyone
+    // may call this; the transfer ends up cancelled and frees the certificate.
+    pub fn cleanup_expired_transfer(env: Env, transfer_id: String) -> Result<(), CertificateError> {
+        let transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())))
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        if transfer.status != TransferStatus::Pending {
+            return Err(CertificateError::TransferNotPending);
+        }
+        if !Self::is_transfer_expired(&env, &transfer) {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        
+        Self::close_expired_transfer(&env, transfer_id, transfer)
+    }
+
+    // Closes every expired pending transfer among `transfer_ids` and returns
+    // how many were closed. Unknown, unexpired or non-pending transfers are
+    // skipped; a failed refund fails the whole batch.
+    pub fn cleanup_expired_transfers(env: Env, transfer_ids: Vec<String>) -> Result<u32, CertificateError> {
+        if transfer_ids.len() > MAX_BATCH_SIZE {
+            return Err(CertificateError::InvalidData);
+        }
+        
+        let mut cleaned = 0;
+        for transfer_id in transfer_ids.iter() {
+            let transfer: TransferRequest = match env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())))
+            {
+                Some(transfer) => transfer,
+                None => continue,
+            };
+            if transfer.status != TransferStatus::Pending || !Self::is_transfer_expired(&env, &transfer) {
+                continue;
+            }
+            Self::close_expired_transfer(&env, transfer_id, transfer)?;
+            cleaned += 1;
+        }
+        Ok(cleaned)
+    }
+
+    // Backs out of an accepted transfer before it completes. Either the
+    // sender or the recipient may rescind; the transfer ends up cancelled.
+    pub fn rescind_transfer(
+        env: Env,
+        transfer_id: String,
+        party: Address,
+    ) -> Result<(), CertificateError> {
+        // Authenticate the rescinding party
+        party.require_auth();
+        
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let mut transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        if party != transfer.from_address && party != transfer.to_address {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        // Pending transfers are withdrawn with cancel or reject instead
+        if transfer.status != TransferStatus::Accepted {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        
+        // Update transfer status
+        Self::set_transfer_status(&env, &mut transfer, TransferStatus::Cancelled);
+        env.storage().instance().set(&transfer_key, &transfer);
+        Self::clear_active_transfer(&env, &transfer.certificate_id);
+        Self::refund_prepaid_fee(&env, &transfer)?;
+        
+        // Emit transfer cancelled event
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_cancel"), transfer.certificate_id.clone()),
+            TransferCancelledEvent {
+                transfer_id,
+                cancelled_at: env.ledger().timestamp(),
+            },
+        );
+        
+        Ok(())
     }
 
-    // Accepts a certificate transfer
-    pub fn accept_transfer(
+    // Rejects a certificate transfer
+    pub fn reject_transfer(
         env: Env,
         transfer_id: String,
         recipient: Address,
@@ -1480,7 +5887,7 @@ impl CertificateContract {
         recipient.require_auth();
         
         // Get the transfer request
-        let transfer_key = DataKey::TransferRequest(transfer_id.clone());
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
         let mut transfer: TransferRequest = env
             .storage()
             .instance()
@@ -1498,271 +5905,807 @@ impl CertificateContract {
         }
         
         // Update transfer status
-        transfer.status = TransferStatus::Accepted;
-        transfer.accepted_at = Some(env.ledger().timestamp());
+        Self::set_transfer_status(&env, &mut transfer, TransferStatus::Rejected);
         env.storage().instance().set(&transfer_key, &transfer);
+        Self::clear_active_transfer(&env, &transfer.certificate_id);
+        Self::refund_prepaid_fee(&env, &transfer)?;
         
         // Remove from pending transfers
-        let pending_key = DataKey::PendingTransfers(recipient.clone());
-        let mut pending_transfers: Vec<String> = env
+        Self::remove_pending_transfer(&env, &recipient, &transfer_id);
+        
+        // Emit transfer rejected event
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_reject"), transfer.certificate_id.clone()),
+            TransferRejectedEvent {
+                transfer_id,
+                rejected_at: env.ledger().timestamp(),
+            },
+        );
+        
+        Ok(())
+    }
+
+    // Rejects every transfer pending acceptance by the recipient and clears
+    // their pending list. Returns the number of transfers rejected.
+    pub fn reject_all_pending(env: Env, recipient: Address) -> Result<u32, CertificateError> {
+        // Authenticate the recipient
+        recipient.require_auth();
+        
+        let pending_key = DataKey::Transfer(TransferKey::PendingTransfers(recipient.clone()));
+        let pending_transfers: Vec<String> = env
             .storage()
             .instance()
             .get(&pending_key)
             .unwrap_or(Vec::new(&env));
         
-        // Remove this transfer from pending list
-        let mut new_pending = Vec::new(&env);
-        for pending_id in pending_transfers.iter() {
-            if pending_id != &transfer_id {
-                new_pending.push_back(pending_id.clone());
-            }
+        let mut rejected = 0u32;
+        for transfer_id in pending_transfers.iter() {
+            let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+            let transfer: Option<TransferRequest> = env.storage().instance().get(&transfer_key);
+            let mut transfer = match transfer {
+                Some(t) if t.status == TransferStatus::Pending && t.to_address == recipient => t,
+                _ => continue,
+            };
+            
+            Self::set_transfer_status(&env, &mut transfer, TransferStatus::Rejected);
+            env.storage().instance().set(&transfer_key, &transfer);
+            Self::clear_active_transfer(&env, &transfer.certificate_id);
+            Self::refund_prepaid_fee(&env, &transfer)?;
+            
+            Self::publish_transfer_event(
+                &env,
+                (symbol_short!("transfer_reject"), transfer.certificate_id.clone()),
+                TransferRejectedEvent {
+                    transfer_id,
+                    rejected_at: env.ledger().timestamp(),
+                },
+            );
+            rejected += 1;
         }
-        env.storage().instance().set(&pending_key, &new_pending);
         
-        // Emit transfer accepted event
-        env.events().publish(
-            (symbol_short!("transfer_accept"),),
-            TransferAcceptedEvent {
-                transfer_id: transfer_id.clone(),
-                accepted_at: transfer.accepted_at.unwrap(),
+        // Clear the pending list in one write
+        Self::decrement_pending_total(&env, rejected);
+        env.storage().instance().remove(&pending_key);
+        
+        Ok(rejected)
+    }
+
+    // Cancels a certificate transfer
+    pub fn cancel_transfer(
+        env: Env,
+        transfer_id: String,
+        sender: Address,
+    ) -> Result<(), CertificateError> {
+        // Authenticate the sender
+        sender.require_auth();
+        
+        // Get the transfer request
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let mut transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        // Verify the sender is the one who initiated the transfer
+        if transfer.from_address != sender {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        // Check if transfer is still pending
+        if transfer.status != TransferStatus::Pending {
+            return Err(CertificateError::TransferNotPending);
+        }
+        
+        // Update transfer status
+        Self::set_transfer_status(&env, &mut transfer, TransferStatus::Cancelled);
+        env.storage().instance().set(&transfer_key, &transfer);
+        Self::clear_active_transfer(&env, &transfer.certificate_id);
+        Self::refund_prepaid_fee(&env, &transfer)?;
+        
+        // Remove from pending transfers
+        Self::remove_pending_transfer(&env, &transfer.to_address, &transfer_id);
+        
+        // Emit transfer cancelled event
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_cancel"), transfer.certificate_id.clone()),
+            TransferCancelledEvent {
+                transfer_id,
+                cancelled_at: env.ledger().timestamp(),
             },
         );
         
         Ok(())
     }
 
-    // Completes a certificate transfer (called after acceptance)
-    pub fn complete_transfer(
+    // Lets the certificate issuer cancel an in-flight transfer, e.g. one they
+    // believe to be fraudulent
+    pub fn issuer_block_transfer(
         env: Env,
         transfer_id: String,
-        executor: Address,
+        issuer: Address,
     ) -> Result<(), CertificateError> {
-        // Authenticate the executor (can be sender, recipient, or admin)
-        executor.require_auth();
+        // Authenticate the issuer
+        issuer.require_auth();
         
         // Get the transfer request
-        let transfer_key = DataKey::TransferRequest(transfer_id.clone());
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
         let mut transfer: TransferRequest = env
             .storage()
             .instance()
             .get(&transfer_key)
             .ok_or(CertificateError::TransferNotFound)?;
         
-        // Check if transfer is accepted
+        // Verify the caller issued the underlying certificate
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&transfer.certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        if cert.issuer != issuer {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        // Only in-flight transfers can be blocked
+        if transfer.status != TransferStatus::Pending && transfer.status != TransferStatus::Accepted {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        let was_pending = transfer.status == TransferStatus::Pending;
+        
+        // Update transfer status
+        Self::set_transfer_status(&env, &mut transfer, TransferStatus::Cancelled);
+        env.storage().instance().set(&transfer_key, &transfer);
+        Self::clear_active_transfer(&env, &transfer.certificate_id);
+        Self::refund_prepaid_fee(&env, &transfer)?;
+        
+        // Accepted transfers were already removed from the pending list
+        if was_pending {
+            Self::remove_pending_transfer(&env, &transfer.to_address, &transfer_id);
+        }
+        
+        // Emit transfer cancelled event
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_cancel"), transfer.certificate_id.clone()),
+            TransferCancelledEvent {
+                transfer_id,
+                cancelled_at: env.ledger().timestamp(),
+            },
+        );
+        
+        Ok(())
+    }
+
+    // Deletes a transfer request that has reached a terminal state to free
+    // storage. Transfer history is left intact, and a tombstone keeps the id
+    // from being reused. Callable by either party or the admin.
+    pub fn purge_transfer(
+        env: Env,
+        transfer_id: String,
+        caller: Address,
+    ) -> Result<(), CertificateError> {
+        caller.require_auth();
+        
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if caller != transfer.from_address
+            && caller != transfer.to_address
+            && admin != Some(caller) {
+            return Err(CertificateError::Unauthorized);
+        }
+        
+        // Active transfers must run to completion or be cancelled first
+        if transfer.status == TransferStatus::Pending || transfer.status == TransferStatus::Accepted {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        
+        env.storage().instance().remove(&transfer_key);
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::UsedTransferId(transfer_id)), &true);
+        
+        Ok(())
+    }
+
+    // Admin override that completes an accepted transfer when both parties
+    // have gone quiet
+    pub fn admin_force_complete(env: Env, transfer_id: String) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        
+        // Get the transfer request
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        // Only accepted transfers can be forced through
         if transfer.status != TransferStatus::Accepted {
             return Err(CertificateError::InvalidTransferStatus);
         }
-        
-        // Get the certificate
-        let mut cert: Certificate = env
+        
+        // Get the certificate
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&transfer.certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        
+        Self::finalize_transfer(&env, transfer, cert)
+    }
+
+    // Admin override that cancels a pending or accepted transfer
+    pub fn admin_force_cancel(env: Env, transfer_id: String) -> Result<(), CertificateError> {
+        Self::require_admin(&env)?;
+        
+        // Get the transfer request
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone()));
+        let mut transfer: TransferRequest = env
+            .storage()
+            .instance()
+            .get(&transfer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+        
+        let was_pending = match transfer.status {
+            TransferStatus::Pending => true,
+            TransferStatus::Accepted => false,
+            _ => return Err(CertificateError::InvalidTransferStatus),
+        };
+        
+        // Update transfer status
+        Self::set_transfer_status(&env, &mut transfer, TransferStatus::Cancelled);
+        env.storage().instance().set(&transfer_key, &transfer);
+        Self::clear_active_transfer(&env, &transfer.certificate_id);
+        Self::refund_prepaid_fee(&env, &transfer)?;
+        
+        // Accepted transfers were already removed from the pending list
+        if was_pending {
+            Self::remove_pending_transfer(&env, &transfer.to_address, &transfer_id);
+        }
+        
+        // Emit transfer cancelled event
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_cancel"), transfer.certificate_id.clone()),
+            TransferCancelledEvent {
+                transfer_id,
+                cancelled_at: env.ledger().timestamp(),
+            },
+        );
+        
+        Ok(())
+    }
+
+    /// Offer a certificate to whoever claims it first, until `expires_at`.
+    /// The offer ID shares the transfer ID namespace, and the offer holds the
+    /// certificate's active transfer slot until it is claimed or cancelled.
+    pub fn create_open_offer(
+        env: Env,
+        offer_id: String,
+        certificate_id: String,
+        from_address: Address,
+        transfer_fee: i128,
+        memo: Option<String>,
+        expires_at: u64,
+    ) -> Result<(), CertificateError> {
+        from_address.require_auth();
+
+        let now = env.ledger().timestamp();
+        if offer_id.len() == 0 || expires_at <= now {
+            return Err(CertificateError::InvalidData);
+        }
+
+        let (min_fee, max_fee) = Self::get_transfer_fee_limits(env.clone());
+        if transfer_fee < 0 || transfer_fee < min_fee || (max_fee != 0 && transfer_fee > max_fee) {
+            return Err(CertificateError::InvalidData);
+        }
+        if let Some(ref m) = memo {
+            if m.len() > MAX_MEMO_LENGTH {
+                return Err(CertificateError::InvalidData);
+            }
+        }
+        if transfer_fee > 0 && memo.is_none() && Self::is_memo_required_on_fee(env.clone()) {
+            return Err(CertificateError::InvalidData);
+        }
+
+        let offer_key = DataKey::Transfer(TransferKey::OpenOffer(offer_id.clone()));
+        let used_key = DataKey::Transfer(TransferKey::UsedTransferId(offer_id.clone()));
+        if env.storage().instance().has(&offer_key)
+            || env.storage().instance().has(&DataKey::Transfer(TransferKey::TransferRequest(offer_id.clone())))
+            || env.storage().instance().has(&used_key)
+        {
+            return Err(CertificateError::AlreadyExists);
+        }
+
+        let cert: Certificate = env
+            .storage()
+            .instance()
+            .get(&certificate_id)
+            .ok_or(CertificateError::NotFound)?;
+        if cert.owner != from_address {
+            return Err(CertificateError::Unauthorized);
+        }
+        Self::ensure_transferable(&cert)?;
+
+        // An offer is an in-flight transfer and excludes any other
+        let active_key = DataKey::Transfer(TransferKey::ActiveTransfer(certificate_id.clone()));
+        if env.storage().instance().has(&active_key) {
+            return Err(CertificateError::TransferAlreadyActive);
+        }
+
+        let offer = OpenOffer {
+            id: offer_id.clone(),
+            certificate_id: certificate_id.clone(),
+            from_address,
+            transfer_fee,
+            memo,
+            created_at: now,
+            expires_at,
+            status: TransferStatus::Pending,
+            claimed_by: None,
+        };
+        env.storage().instance().set(&offer_key, &offer);
+        env.storage().instance().set(&used_key, &true);
+        env.storage().instance().set(&active_key, &offer_id);
+
+        Self::publish_transfer_event(&env, (symbol_short!("offer_new"), certificate_id), offer);
+        Ok(())
+    }
+
+    /// Withdraw an unclaimed offer and free the certificate for other
+    /// transfers. The owner may withdraw at any time; once the offer has
+    /// expired anyone may clear it.
+    pub fn cancel_open_offer(env: Env, offer_id: String, caller: Address) -> Result<(), CertificateError> {
+        caller.require_auth();
+
+        let offer_key = DataKey::Transfer(TransferKey::OpenOffer(offer_id.clone()));
+        let mut offer: OpenOffer = env
+            .storage()
+            .instance()
+            .get(&offer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+
+        if offer.status != TransferStatus::Pending {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        if caller != offer.from_address && env.ledger().timestamp() <= offer.expires_at {
+            return Err(CertificateError::Unauthorized);
+        }
+
+        offer.status = TransferStatus::Cancelled;
+        env.storage().instance().set(&offer_key, &offer);
+        Self::clear_active_transfer(&env, &offer.certificate_id);
+
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("offer_end"), offer.certificate_id.clone()),
+            TransferCancelledEvent {
+                transfer_id: offer_id,
+                cancelled_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Claim an open offer, transferring the certificate to `claimer` in the
+    /// same call. Only the first successful claim takes effect.
+    pub fn claim_offer(env: Env, offer_id: String, claimer: Address) -> Result<(), CertificateError> {
+        claimer.require_auth();
+
+        let offer_key = DataKey::Transfer(TransferKey::OpenOffer(offer_id.clone()));
+        let mut offer: OpenOffer = env
+            .storage()
+            .instance()
+            .get(&offer_key)
+            .ok_or(CertificateError::TransferNotFound)?;
+
+        if offer.status != TransferStatus::Pending {
+            return Err(CertificateError::InvalidTransferStatus);
+        }
+        let now = env.ledger().timestamp();
+        if now > offer.expires_at {
+            return Err(CertificateError::TransferExpired);
+        }
+
+        // The owner, certificate and claimer must still pass every transfer
+        // check. The offer holds the active transfer slot, so release it for
+        // the check; a failed claim reverts the release.
+        let active: Option<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::ActiveTransfer(offer.certificate_id.clone())));
+        if active == Some(offer_id.clone()) {
+            Self::clear_active_transfer(&env, &offer.certificate_id);
+        }
+        let cert = Self::validate_transfer(&env, &offer.certificate_id, &offer.from_address, &claimer)?;
+        Self::ensure_transfer_approved(&env, &offer.certificate_id, Some(&offer_id))?;
+
+        let transfer = TransferRequest {
+            id: offer_id.clone(),
+            certificate_id: offer.certificate_id.clone(),
+            from_address: offer.from_address.clone(),
+            to_address: claimer.clone(),
+            initiated_at: offer.created_at,
+            accepted_at: Some(now),
+            completed_at: None,
+            status: TransferStatus::Accepted,
+            require_revocation: false,
+            transfer_fee: offer.transfer_fee,
+            memo: offer.memo.clone(),
+            notify_recipient: false,
+            complete_by: None,
+            completer_policy: CompleterPolicy::AnyParty,
+            expires_at: Some(offer.expires_at),
+            data: None,
+            prepaid_token: None,
+        };
+        Self::count_transfer_status(&env, None, &transfer.status);
+
+        let cert_transfers_key = DataKey::Transfer(TransferKey::CertTransfers(offer.certificate_id.clone()));
+        let mut cert_transfers: Vec<String> = env
             .storage()
             .instance()
-            .get(&transfer.certificate_id)
-            .ok_or(CertificateError::NotFound)?;
+            .get(&cert_transfers_key)
+            .unwrap_or(Vec::new(&env));
+        cert_transfers.push_back(offer_id.clone());
+        env.storage().instance().set(&cert_transfers_key, &cert_transfers);
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::ActiveTransfer(offer.certificate_id.clone())), &offer_id);
+        Self::increment_transfer_count(&env)?;
+
+        offer.status = TransferStatus::Completed;
+        offer.claimed_by = Some(claimer);
+        env.storage().instance().set(&offer_key, &offer);
+
+        Self::finalize_transfer(&env, transfer, cert)
+    }
+
+    /// Get an open offer by ID
+    pub fn get_open_offer(env: Env, offer_id: String) -> Option<OpenOffer> {
+        env.storage().instance().get(&DataKey::Transfer(TransferKey::OpenOffer(offer_id)))
+    }
+
+    // Transfers a certificate immediately, skipping the accept/complete steps.
+    // No pending request is created; the history entry and completion event use
+    // a generated transfer id of the form "direct-<transfer count>".
+    pub fn direct_transfer(
+        env: Env,
+        certificate_id: String,
+        from_address: Address,
+        to_address: Address,
+        require_revocation: bool,
+    ) -> Result<(), CertificateError> {
+        // Authenticate the current owner
+        from_address.require_auth();
         
-        // Verify authorization (sender, recipient, or issuer can complete)
-        if executor != transfer.from_address 
-            && executor != transfer.to_address 
-            && executor != cert.issuer {
-            return Err(CertificateError::Unauthorized);
-        }
+        // Validate ownership, certificate state and recipient
+        let mut cert = Self::validate_transfer(&env, &certificate_id, &from_address, &to_address)?;
+        Self::ensure_transfer_approved(&env, &certificate_id, None)?;
+        Self::ensure_owner_capacity(&env, &to_address)?;
+        
+        let now = env.ledger().timestamp();
         
         // Revoke certificate if required
-        if transfer.require_revocation {
-            cert.revoked = true;
-            cert.revocation_reason = Some(String::from_str(&env, "Transferred to new owner"));
-            cert.revoked_at = Some(env.ledger().timestamp());
-            cert.revoked_by = Some(transfer.from_address.clone());
-            env.storage().instance().set(&transfer.certificate_id, &cert);
+        if require_revocation {
+            Self::mark_revoked(
+                &env,
+                &mut cert,
+                String::from_str(&env, "Transferred to new owner"),
+                from_address.clone(),
+            );
         }
         
         // Update certificate owner
-        cert.owner = transfer.to_address.clone();
-        env.storage().instance().set(&transfer.certificate_id, &cert);
-        
-        // Update transfer status to completed
-        transfer.status = TransferStatus::Completed;
-        transfer.completed_at = Some(env.ledger().timestamp());
-        env.storage().instance().set(&transfer_key, &transfer);
+        cert.owner = to_address.clone();
+        Self::count_completed_transfer(&env, &mut cert);
+        Self::save_certificate(&env, &mut cert);
+        Self::move_owner_index(&env, &certificate_id, &from_address, &to_address);
+        Self::emit_ownership_changed(
+            &env,
+            &certificate_id,
+            from_address.clone(),
+            Some(to_address.clone()),
+            OwnershipChangeCause::DirectTransfer,
+        );
         
-        // Add to transfer history
-        let history_key = DataKey::TransferHistory(transfer.certificate_id.clone());
-        let mut history: Vec<TransferHistory> = env
-            .storage()
+        // Derive a unique id from the transfer counter, skipping any a caller
+        // already chose for its own transfer, and reserve it
+        Self::increment_transfer_count(&env)?;
+        let mut sequence = Self::get_transfer_count(env.clone());
+        let mut transfer_id = Self::direct_transfer_id(&env, sequence);
+        while Self::transfer_exists(env.clone(), transfer_id.clone()) {
+            sequence += 1;
+            transfer_id = Self::direct_transfer_id(&env, sequence);
+        }
+        env.storage()
             .instance()
-            .get(&history_key)
-            .unwrap_or(Vec::new(&env));
-        
-        let transfer_history = TransferHistory {
-            transfer_id: transfer_id.clone(),
-            certificate_id: transfer.certificate_id.clone(),
-            from_address: transfer.from_address.clone(),
-            to_address: transfer.to_address.clone(),
-            transferred_at: transfer.completed_at.unwrap(),
-            transfer_fee: transfer.transfer_fee,
-            memo: transfer.memo.clone(),
-        };
+            .set(&DataKey::Transfer(TransferKey::UsedTransferId(transfer_id.clone())), &true);
         
-        history.push_back(transfer_history);
-        env.storage().instance().set(&history_key, &history);
+        Self::record_transfer_history(
+            &env,
+            TransferHistory {
+                transfer_id: transfer_id.clone(),
+                certificate_id: certificate_id.clone(),
+                from_address: from_address.clone(),
+                to_address: to_address.clone(),
+                transferred_at: now,
+                transfer_fee: 0,
+                memo: None,
+                data: None,
+            },
+        );
         
         // Emit transfer completed event
-        env.events().publish(
-            (symbol_short!("transfer_complete"),),
+        Self::publish_transfer_event(
+            &env,
+            (symbol_short!("transfer_complete"), certificate_id.clone()),
             TransferCompletedEvent {
-                transfer_id: transfer_id.clone(),
-                certificate_id: transfer.certificate_id,
-                from_address: transfer.from_address,
-                to_address: transfer.to_address,
-                completed_at: transfer.completed_at.unwrap(),
-                transfer_fee: transfer.transfer_fee,
+                transfer_id,
+                certificate_id,
+                from_address,
+                to_address,
+                completed_at: now,
+                transfer_fee: 0,
             },
         );
         
         Ok(())
     }
 
-    // Rejects a certificate transfer
-    pub fn reject_transfer(
-        env: Env,
-        transfer_id: String,
-        recipient: Address,
-    ) -> Result<(), CertificateError> {
-        // Authenticate the recipient
-        recipient.require_auth();
-        
-        // Get the transfer request
-        let transfer_key = DataKey::TransferRequest(transfer_id.clone());
-        let mut transfer: TransferRequest = env
-            .storage()
+    // Query functions
+    
+    // Get a transfer request by ID
+    pub fn get_transfer(env: Env, transfer_id: String) -> Result<TransferRequest, CertificateError> {
+        let transfer_key = DataKey::Transfer(TransferKey::TransferRequest(transfer_id));
+        env.storage()
             .instance()
             .get(&transfer_key)
-            .ok_or(CertificateError::TransferNotFound)?;
-        
-        // Verify the recipient is the intended recipient
-        if transfer.to_address != recipient {
-            return Err(CertificateError::Unauthorized);
-        }
-        
-        // Check if transfer is still pending
-        if transfer.status != TransferStatus::Pending {
-            return Err(CertificateError::TransferNotPending);
-        }
-        
-        // Update transfer status
-        transfer.status = TransferStatus::Rejected;
-        env.storage().instance().set(&transfer_key, &transfer);
-        
-        // Remove from pending transfers
-        let pending_key = DataKey::PendingTransfers(recipient);
-        let mut pending_transfers: Vec<String> = env
-            .storage()
+            .ok_or(CertificateError::TransferNotFound)
+    }
+
+    // Get the id of the pending or accepted transfer for a certificate, if any
+    pub fn get_active_transfer(env: Env, certificate_id: String) -> Option<String> {
+        env.storage()
             .instance()
-            .get(&pending_key)
-            .unwrap_or(Vec::new(&env));
-        
-        let mut new_pending = Vec::new(&env);
-        for pending_id in pending_transfers.iter() {
-            if pending_id != &transfer_id {
-                new_pending.push_back(pending_id.clone());
-            }
+            .get(&DataKey::Transfer(TransferKey::ActiveTransfer(certificate_id)))
+    }
+
+    // Check whether a transfer id is already in use, including ids of
+    // transfers that have since been purged
+    pub fn transfer_exists(env: Env, transfer_id: String) -> bool {
+        env.storage()
+            .instance()
+            .has(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())))
+            || env.storage().instance().has(&DataKey::Transfer(TransferKey::UsedTransferId(transfer_id)))
+    }
+
+    // Get only the status of a transfer request
+    pub fn get_transfer_status(env: Env, transfer_id: String) -> Result<TransferStatus, CertificateError> {
+        let transfer = Self::get_transfer(env, transfer_id)?;
+        Ok(transfer.status)
+    }
+
+    // Get the current status of many transfers at once, in the order given.
+    // Unknown or purged transfers yield `None` in their position.
+    pub fn get_transfer_statuses(
+        env: Env,
+        transfer_ids: Vec<String>,
+    ) -> Result<Vec<Option<TransferStatus>>, CertificateError> {
+        if transfer_ids.len() > MAX_BATCH_SIZE {
+            return Err(CertificateError::InvalidData);
         }
-        env.storage().instance().set(&pending_key, &new_pending);
-        
-        // Emit transfer rejected event
-        env.events().publish(
-            (symbol_short!("transfer_reject"),),
-            TransferRejectedEvent {
-                transfer_id,
-                rejected_at: env.ledger().timestamp(),
-            },
-        );
         
-        Ok(())
+        let mut statuses = Vec::new(&env);
+        for transfer_id in transfer_ids.iter() {
+            let transfer: Option<TransferRequest> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id)));
+            statuses.push_back(transfer.map(|t| t.status));
+        }
+        Ok(statuses)
     }
 
-    // Cancels a certificate transfer
-    pub fn cancel_transfer(
+    // Get only the timestamps of a transfer request as (initiated, accepted, completed)
+    pub fn get_transfer_timestamps(
         env: Env,
         transfer_id: String,
-        sender: Address,
+    ) -> Result<(u64, Option<u64>, Option<u64>), CertificateError> {
+        let transfer = Self::get_transfer(env, transfer_id)?;
+        Ok((transfer.initiated_at, transfer.accepted_at, transfer.completed_at))
+    }
+
+    // Dry-run the checks performed by initiate_transfer without requiring auth
+    // or mutating state
+    pub fn can_transfer(
+        env: Env,
+        certificate_id: String,
+        from_address: Address,
+        to_address: Address,
     ) -> Result<(), CertificateError> {
-        // Authenticate the sender
-        sender.require_auth();
-        
-        // Get the transfer request
-        let transfer_key = DataKey::TransferRequest(transfer_id.clone());
-        let mut transfer: TransferRequest = env
+        Self::validate_transfer(&env, &certificate_id, &from_address, &to_address)?;
+        Ok(())
+    }
+
+    // Check whether a certificate could change hands right now: it must not
+    // be revoked, expired, frozen, suspended or renounced, and must have no
+    // transfer in flight
+    pub fn is_transferable_now(env: Env, id: String) -> Result<bool, CertificateError> {
+        let cert: Certificate = env
             .storage()
             .instance()
-            .get(&transfer_key)
-            .ok_or(CertificateError::TransferNotFound)?;
-        
-        // Verify the sender is the one who initiated the transfer
-        if transfer.from_address != sender {
-            return Err(CertificateError::Unauthorized);
-        }
-        
-        // Check if transfer is still pending
-        if transfer.status != TransferStatus::Pending {
-            return Err(CertificateError::TransferNotPending);
-        }
-        
-        // Update transfer status
-        transfer.status = TransferStatus::Cancelled;
-        env.storage().instance().set(&transfer_key, &transfer);
+            .get(&id)
+            .ok_or(CertificateError::NotFound)?;
+
+        Ok(Self::ensure_transferable(&cert).is_ok()
+            && !Self::is_expired(&env, &cert)
+            && !Self::is_scheduled_revocation_due(&env, &cert)
+            && !Self::is_too_new_to_transfer(&env, &cert)
+            && !env.storage().instance().has(&DataKey::Transfer(TransferKey::ActiveTransfer(id))))
+    }
+
+    // Get pending transfers for an address
+    pub fn get_pending_transfers(env: Env, address: Address) -> Vec<String> {
+        let pending_key = DataKey::Transfer(TransferKey::PendingTransfers(address));
+        env.storage()
+            .instance()
+            .get(&pending_key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Reconciles an address's pending list against the stored transfer
+    // requests, dropping ids that are unknown, no longer pending or listed
+    // twice, and takes the dropped entries off the global pending total.
+    // Returns the number of entries removed.
+    pub fn repair_pending_index(env: Env, address: Address) -> Result<u32, CertificateError> {
+        Self::require_admin(&env)?;
         
-        // Remove from pending transfers
-        let pending_key = DataKey::PendingTransfers(transfer.to_address);
-        let mut pending_transfers: Vec<String> = env
+        let pending_key = DataKey::Transfer(TransferKey::PendingTransfers(address));
+        let pending_transfers: Vec<String> = env
             .storage()
             .instance()
             .get(&pending_key)
             .unwrap_or(Vec::new(&env));
         
-        let mut new_pending = Vec::new(&env);
-        for pending_id in pending_transfers.iter() {
-            if pending_id != &transfer_id {
-                new_pending.push_back(pending_id.clone());
+        let mut repaired = Vec::new(&env);
+        for transfer_id in pending_transfers.iter() {
+            let transfer: Option<TransferRequest> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id.clone())));
+            let still_pending = matches!(transfer, Some(t) if t.status == TransferStatus::Pending);
+            if still_pending && !repaired.contains(&transfer_id) {
+                repaired.push_back(transfer_id);
             }
         }
-        env.storage().instance().set(&pending_key, &new_pending);
         
-        // Emit transfer cancelled event
-        env.events().publish(
-            (symbol_short!("transfer_cancel"),),
-            TransferCancelledEvent {
-                transfer_id,
-                cancelled_at: env.ledger().timestamp(),
-            },
-        );
+        let removed = pending_transfers.len() - repaired.len();
+        env.storage().instance().set(&pending_key, &repaired);
+        Self::decrement_pending_total(&env, removed);
+        Ok(removed)
+    }
+
+    // Backfills the owner index for certificates stored before it existed.
+    // Each certificate is indexed under its current owner unless already
+    // there, so the call is safe to repeat. Returns the number of entries added.
+    pub fn rebuild_owner_index(env: Env, ids: Vec<String>) -> Result<u32, CertificateError> {
+        Self::require_admin(&env)?;
         
-        Ok(())
+        let mut added = 0u32;
+        for id in ids.iter() {
+            let cert: Option<Certificate> = env.storage().instance().get(&id);
+            let cert = match cert {
+                Some(cert) => cert,
+                None => continue,
+            };
+            let owned: Vec<String> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Index(IndexKey::OwnerCertificates(cert.owner.clone())))
+                .unwrap_or(Vec::new(&env));
+            if !owned.contains(&id) {
+                Self::add_to_owner_index(&env, &cert.owner, &id);
+                added += 1;
+            }
+        }
+        Ok(added)
     }
 
-    // Query functions
-    
-    // Get a transfer request by ID
-    pub fn get_transfer(env: Env, transfer_id: String) -> Result<TransferRequest, CertificateError> {
-        let transfer_key = DataKey::TransferRequest(transfer_id);
+    // Get the number of transfer requests in each status, for dashboards
+    pub fn get_status_counts(env: Env) -> StatusCounts {
         env.storage()
             .instance()
-            .get(&transfer_key)
-            .ok_or(CertificateError::TransferNotFound)
+            .get(&DataKey::Transfer(TransferKey::StatusCounts))
+            .unwrap_or_default()
     }
 
-    // Get pending transfers for an address
-    pub fn get_pending_transfers(env: Env, address: Address) -> Vec<String> {
-        let pending_key = DataKey::PendingTransfers(address);
-        env.storage()
+    // Get the number of transfers pending acceptance by an address
+    pub fn get_pending_transfer_count(env: Env, address: Address) -> u32 {
+        Self::get_pending_transfers(env, address).len()
+    }
+
+    // Get every transfer request initiated for a certificate, in any status.
+    // Purged requests are skipped.
+    pub fn get_transfers_for_certificate(env: Env, certificate_id: String) -> Vec<TransferRequest> {
+        let transfer_ids: Vec<String> = env
+            .storage()
             .instance()
-            .get(&pending_key)
-            .unwrap_or(Vec::new(&env))
+            .get(&DataKey::Transfer(TransferKey::CertTransfers(certificate_id)))
+            .unwrap_or(Vec::new(&env));
+
+        let mut transfers = Vec::new(&env);
+        for transfer_id in transfer_ids.iter() {
+            let transfer: Option<TransferRequest> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id)));
+            if let Some(transfer) = transfer {
+                transfers.push_back(transfer);
+            }
+        }
+        transfers
+    }
+
+    // Find the first transfer for a certificate in the given status, in
+    // initiation order
+    pub fn find_transfer(
+        env: Env,
+        certificate_id: String,
+        status: TransferStatus,
+    ) -> Option<TransferRequest> {
+        let transfer_ids: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Transfer(TransferKey::CertTransfers(certificate_id)))
+            .unwrap_or(Vec::new(&env));
+
+        for transfer_id in transfer_ids.iter() {
+            let transfer: Option<TransferRequest> = env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::TransferRequest(transfer_id)));
+            if let Some(transfer) = transfer {
+                if transfer.status == status {
+                    return Some(transfer);
+                }
+            }
+        }
+        None
     }
 
     // Get transfer history for a certificate
     pub fn get_transfer_history(env: Env, certificate_id: String) -> Vec<TransferHistory> {
-        let history_key = DataKey::TransferHistory(certificate_id);
+        let history_key = DataKey::Transfer(TransferKey::TransferHistory(certificate_id));
+        env.storage()
+            .instance()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Sum the fees of every completed transfer of a certificate. Walks the
+    // full transfer history, so cost grows linearly with the number of transfers.
+    pub fn get_total_fees_for_certificate(env: Env, certificate_id: String) -> i128 {
+        let history = Self::get_transfer_history(env, certificate_id);
+        let mut total: i128 = 0;
+        for entry in history.iter() {
+            total = total.saturating_add(entry.transfer_fee);
+        }
+        total
+    }
+
+    // Get completed transfers sent or received by an address, across all certificates
+    pub fn get_transfer_history_for_address(env: Env, address: Address) -> Vec<TransferHistory> {
+        let history_key = DataKey::Transfer(TransferKey::AddressHistory(address));
         env.storage()
             .instance()
             .get(&history_key)
@@ -1773,10 +6716,36 @@ impl CertificateContract {
     pub fn get_transfer_count(env: Env) -> u64 {
         env.storage()
             .instance()
-            .get(&DataKey::TransferCount)
+            .get(&DataKey::Transfer(TransferKey::TransferCount))
             .unwrap_or(0)
     }
 
+    // Get the index the next created certificate will receive. Backed by a
+    // dedicated key that is only ever incremented, so it never decreases.
+    pub fn get_next_certificate_index(env: Env) -> u64 {
+        Self::current_certificate_index(&env)
+    }
+
+    // Get headline counters in a single call
+    pub fn get_contract_stats(env: Env) -> ContractStats {
+        let issuance_log: Vec<(String, u64)> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Index(IndexKey::IssuanceLog))
+            .unwrap_or(Vec::new(&env));
+
+        ContractStats {
+            total_certificates: issuance_log.len(),
+            total_revoked: Self::get_revoked_count(env.clone()) as u32,
+            total_transfers: Self::get_transfer_count(env.clone()),
+            pending_transfers: env
+                .storage()
+                .instance()
+                .get(&DataKey::Transfer(TransferKey::PendingTransferTotal))
+                .unwrap_or(0),
+        }
+    }
+
     // Query functions for upgrades
     
     // Get an upgrade request by ID
@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, Vec};
+use soroban_sdk::{testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke}, Address, Bytes, BytesN, Env, IntoVal, Vec};
 use soroban_sdk::{Env, testutils::Address as _, Address, String};
 
 // Helper function to create a certificate version
@@ -59,7 +59,7 @@ fn create_test_certificate(
         owner: owner.clone(),
         metadata_uri: metadata_uri.clone(),
         issued_at: env.ledger().timestamp(),
-        revoked: false,
+        flags: 0,
         revocation_reason: None,
         revoked_at: None,
         revoked_by: None,
@@ -69,6 +69,20 @@ fn create_test_certificate(
         is_upgradable: true,
         upgrade_rules,
         compatibility_matrix,
+        freeze_info: None,
+        expires_at: None,
+        suspension_reason: None,
+        nonce: 0,
+        metadata_hash: None,
+        last_modified_at: env.ledger().timestamp(),
+        scheduled_revocation_at: None,
+        scheduled_revocation_reason: None,
+        supersedes: None,
+        superseded_by: None,
+        backup_uris: Vec::new(env),
+        max_transfers: None,
+        transfer_count: 0,
+        verifier: None,
     }
 }
 
@@ -84,7 +98,7 @@ fn test_issue_and_revoke() {
     let metadata_uri = String::from_str(&env, "ipfs://Qm...");
 
     env.mock_all_auths();
-    client.issue_certificate(&id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&id, &issuer, &owner, &metadata_uri, &None);
 
     let cert = client.get_certificate(&id);
     assert_eq!(cert.id, id);
@@ -119,9 +133,9 @@ fn test_certificate_transfer_flow() {
 
     env.mock_all_auths();
 
-    client.issue_certificate(&id1, &issuer, &owner, &metadata_uri);
-    client.issue_certificate(&id2, &issuer, &owner, &metadata_uri);
-    client.issue_certificate(&id3, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&id1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id2, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id3, &issuer, &owner, &metadata_uri, &None);
 
     let revoke_reason = String::from_str(&env, "policy");
     client.revoke_certificate(&id2, &revoke_reason);
@@ -174,7 +188,7 @@ fn test_verify_merkle_batch_with_partial_success() {
     env.mock_all_auths();
     
     // Issue certificate
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Verify initial owner
     let cert = client.get_certificate(&cert_id);
@@ -189,6 +203,8 @@ fn test_verify_merkle_batch_with_partial_success() {
         &false, // don't revoke on transfer
         &0u64,  // no transfer fee
         &None,  // no memo
+        &false,
+        &None,
     );
     
     // Verify transfer is pending
@@ -246,7 +262,7 @@ fn test_transfer_with_revocation() {
     env.mock_all_auths();
     
     // Issue certificate
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Initiate transfer with revocation
     client.initiate_transfer(
@@ -257,6 +273,8 @@ fn test_transfer_with_revocation() {
         &true,  // revoke on transfer
         &0u64,  // no transfer fee
         &None,  // no memo
+        &false,
+        &None,
     );
     
     // Accept and complete transfer
@@ -286,7 +304,7 @@ fn test_transfer_rejection() {
     env.mock_all_auths();
     
     // Issue certificate
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Initiate transfer
     client.initiate_transfer(
@@ -297,6 +315,8 @@ fn test_transfer_rejection() {
         &false,
         &0u64,
         &None,
+        &false,
+        &None,
     );
     
     // Reject transfer
@@ -327,7 +347,7 @@ fn test_transfer_cancellation() {
     env.mock_all_auths();
     
     // Issue certificate
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Initiate transfer
     client.initiate_transfer(
@@ -338,6 +358,8 @@ fn test_transfer_cancellation() {
         &false,
         &0u64,
         &None,
+        &false,
+        &None,
     );
     
     // Cancel transfer
@@ -369,7 +391,7 @@ fn test_transfer_with_fee() {
     env.mock_all_auths();
     
     // Issue certificate
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Initiate transfer with fee
     client.initiate_transfer(
@@ -380,6 +402,8 @@ fn test_transfer_with_fee() {
         &false,
         &transfer_fee,
         &Some(String::from_str(&env, "Transfer with fee")),
+        &false,
+        &None,
     );
     
     // Accept and complete transfer
@@ -390,7 +414,7 @@ fn test_transfer_with_fee() {
     let history = client.get_transfer_history(&cert_id);
     assert_eq!(history.len(), 1);
     let history_entry = history.get(0);
-    assert_eq!(history_entry.transfer_fee, transfer_fee);
+    assert_eq!(history_entry.transfer_fee, transfer_fee as i128);
     assert_eq!(history_entry.memo, Some(String::from_str(&env, "Transfer with fee")));
 }
 
@@ -411,7 +435,7 @@ fn test_transfer_authorization() {
     env.mock_all_auths();
     
     // Issue certificate
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Try to initiate transfer from unauthorized address
     let result = client.try_initiate_transfer(
@@ -422,6 +446,8 @@ fn test_transfer_authorization() {
         &false,
         &0u64,
         &None,
+        &false,
+        &None,
     );
     
     // Should fail with Unauthorized error
@@ -436,6 +462,8 @@ fn test_transfer_authorization() {
         &false,
         &0u64,
         &None,
+        &false,
+        &None,
     );
     
     let result2 = client.try_accept_transfer(&transfer_id, &unauthorized);
@@ -488,7 +516,7 @@ fn test_transfer_count() {
     assert_eq!(client.get_transfer_count(), 0);
     
     // Issue certificate and make transfers
-    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
     
     // Make 3 transfers
     for i in 1..=3 {
@@ -503,6 +531,8 @@ fn test_transfer_count() {
             &false,
             &0u64,
             &None,
+            &false,
+            &None,
         );
         client.accept_transfer(&transfer_id, &new_recipient);
         client.complete_transfer(&transfer_id, &owner);
@@ -832,3 +862,4537 @@ fn test_upgrade_count() {
     // Upgrade count should be 3
     assert_eq!(client.get_upgrade_count(), 3);
 }
+
+#[test]
+fn test_renounce_certificate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-renounce");
+    let metadata_uri = String::from_str(&env, "ipfs://QmRenounce");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    assert!(!client.is_renounced(&cert_id));
+
+    // Only the owner can renounce
+    let result = client.try_renounce_certificate(&cert_id, &new_owner);
+    assert!(result.is_err());
+
+    client.renounce_certificate(&cert_id, &owner);
+    assert!(client.is_renounced(&cert_id));
+    assert_eq!(client.get_certificate(&cert_id).renounced, true);
+
+    // Renouncing twice fails
+    let result = client.try_renounce_certificate(&cert_id, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRenounced)));
+
+    // A renounced certificate cannot be transferred
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, "transfer-renounce"),
+        &cert_id,
+        &owner,
+        &new_owner,
+        &false,
+        &0u64,
+        &None,
+        &false,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRenounced)));
+
+    // The issuer can still revoke it
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Membership ended"));
+    assert!(client.is_revoked(&cert_id));
+}
+
+#[test]
+fn test_initiate_transfer_rejection_reasons() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-reasons");
+    let metadata_uri = String::from_str(&env, "ipfs://QmReasons");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    // Sender and recipient are the same
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, "transfer-self"),
+        &cert_id,
+        &owner,
+        &owner,
+        &false,
+        &0u64,
+        &None,
+        &false,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::SelfTransferNotAllowed)));
+
+    // Empty transfer id is malformed input
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, ""),
+        &cert_id,
+        &owner,
+        &new_owner,
+        &false,
+        &0u64,
+        &None,
+        &false,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+}
+
+#[test]
+fn test_issue_certificate_input_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmValid");
+
+    env.mock_all_auths();
+
+    // Empty id is rejected
+    let result = client.try_issue_certificate(
+        &String::from_str(&env, ""),
+        &issuer,
+        &owner,
+        &metadata_uri,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Empty metadata URI is rejected
+    let result = client.try_issue_certificate(
+        &String::from_str(&env, "cert-empty-uri"),
+        &issuer,
+        &owner,
+        &String::from_str(&env, ""),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Overlong id is rejected
+    let long_id = String::from_str(&env, &"a".repeat(65));
+    let result = client.try_issue_certificate(&long_id, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // A well-formed issuance still succeeds
+    let cert_id = String::from_str(&env, "cert-valid");
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_certificate(&cert_id).id, cert_id);
+
+    // Re-issuing the same id is reported as a duplicate
+    let result = client.try_issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+}
+
+#[test]
+fn test_transfer_memo_length_limit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-memo");
+    let metadata_uri = String::from_str(&env, "ipfs://QmMemo");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    // One byte over the limit is rejected
+    let too_long = String::from_str(&env, &"m".repeat(257));
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, "transfer-memo-long"),
+        &cert_id,
+        &owner,
+        &new_owner,
+        &false,
+        &0u64,
+        &Some(too_long),
+        &false,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // A memo exactly at the limit is accepted
+    let at_limit = String::from_str(&env, &"m".repeat(256));
+    let transfer_id = String::from_str(&env, "transfer-memo-ok");
+    client.initiate_transfer(
+        &transfer_id,
+        &cert_id,
+        &owner,
+        &new_owner,
+        &false,
+        &0u64,
+        &Some(at_limit.clone()),
+        &false,
+        &None,
+    );
+    assert_eq!(client.get_transfer(&transfer_id).memo, Some(at_limit));
+}
+
+#[test]
+fn test_direct_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-direct");
+    let metadata_uri = String::from_str(&env, "ipfs://QmDirect");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    // Ownership changes in a single call
+    client.direct_transfer(&cert_id, &owner, &new_owner, &false);
+
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.owner, new_owner);
+    assert_eq!(cert.revoked, false);
+
+    // No pending request was created, but history records the transfer
+    assert_eq!(client.get_pending_transfers(&new_owner).len(), 0);
+    let history = client.get_transfer_history(&cert_id);
+    assert_eq!(history.len(), 1);
+    let entry = history.get(0).unwrap();
+    assert_eq!(entry.from_address, owner);
+    assert_eq!(entry.to_address, new_owner);
+    assert_eq!(entry.transfer_id, String::from_str(&env, "direct-1"));
+
+    // Each direct transfer gets its own id
+    client.direct_transfer(&cert_id, &new_owner, &owner, &false);
+    client.direct_transfer(&cert_id, &owner, &new_owner, &false);
+    let history = client.get_transfer_history(&cert_id);
+    assert_eq!(history.get(1).unwrap().transfer_id, String::from_str(&env, "direct-2"));
+    assert_eq!(history.get(2).unwrap().transfer_id, String::from_str(&env, "direct-3"));
+
+    // The previous owner can no longer move it
+    let result = client.try_direct_transfer(&cert_id, &owner, &issuer, &false);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    // Frozen certificates cannot be transferred directly
+    client.freeze_certificate(&cert_id, &issuer, &String::from_str(&env, "Dispute"), &7);
+    let result = client.try_direct_transfer(&cert_id, &new_owner, &owner, &false);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateFrozen)));
+
+    // Generated ids skip ones a caller already used
+    let pending_cert = String::from_str(&env, "cert-direct-pending");
+    let later_cert = String::from_str(&env, "cert-direct-later");
+    client.issue_certificate(&pending_cert, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&later_cert, &issuer, &owner, &metadata_uri, &None);
+    let taken_id = String::from_str(&env, "direct-5");
+    client.initiate_transfer(&taken_id, &pending_cert, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.direct_transfer(&later_cert, &owner, &new_owner, &false);
+    let history = client.get_transfer_history(&later_cert);
+    assert_eq!(history.get(0).unwrap().transfer_id, String::from_str(&env, "direct-6"));
+}
+
+#[test]
+fn test_is_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-owner");
+    let metadata_uri = String::from_str(&env, "ipfs://QmOwner");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    assert!(client.is_owner(&cert_id, &owner));
+    assert!(!client.is_owner(&cert_id, &stranger));
+
+    let result = client.try_is_owner(&String::from_str(&env, "missing-cert"), &owner);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+
+    // Renouncing ends ownership
+    client.renounce_certificate(&cert_id, &owner);
+    assert!(!client.is_owner(&cert_id, &owner));
+}
+
+#[test]
+fn test_expiry_auto_revocation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let auto_id = String::from_str(&env, "cert-auto-revoke");
+    let plain_id = String::from_str(&env, "cert-expires");
+    let metadata_uri = String::from_str(&env, "ipfs://QmExpiry");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.issue_certificate_with_expiry(&auto_id, &issuer, &owner, &metadata_uri, &2_000, &true);
+    client.issue_certificate_with_expiry(&plain_id, &issuer, &owner, &metadata_uri, &2_000, &false);
+
+    // Both are valid before expiry
+    assert_eq!(client.verify_certificate(&auto_id).status, CertificateStatus::Valid);
+    assert_eq!(client.verify_certificate(&plain_id).status, CertificateStatus::Valid);
+    assert!(!client.is_revoked(&auto_id));
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+
+    // Auto-revoke certificate reads as revoked with a synthetic reason
+    let result = client.verify_certificate(&auto_id);
+    assert_eq!(result.status, CertificateStatus::Revoked);
+    assert_eq!(result.revocation_reason, Some(RevocationReason::Expired));
+    assert!(client.is_revoked(&auto_id));
+
+    // Storage is untouched
+    assert_eq!(client.get_certificate(&auto_id).revoked, false);
+
+    // Plain certificate reads as expired, not revoked
+    let result = client.verify_certificate(&plain_id);
+    assert_eq!(result.status, CertificateStatus::Expired);
+    assert_eq!(result.revocation_reason, None);
+    assert!(!client.is_revoked(&plain_id));
+}
+
+#[test]
+fn test_transfer_history_for_address() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let sent_id = String::from_str(&env, "cert-sent");
+    let received_id = String::from_str(&env, "cert-received");
+    let metadata_uri = String::from_str(&env, "ipfs://QmHistory");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&sent_id, &issuer, &alice, &metadata_uri, &None);
+    client.issue_certificate(&received_id, &issuer, &bob, &metadata_uri, &None);
+
+    // Alice sends one certificate to Bob
+    let transfer_out = String::from_str(&env, "transfer-out");
+    client.initiate_transfer(&transfer_out, &sent_id, &alice, &bob, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_out, &bob);
+    client.complete_transfer(&transfer_out, &alice);
+
+    // Bob sends another certificate to Alice
+    let transfer_in = String::from_str(&env, "transfer-in");
+    client.initiate_transfer(&transfer_in, &received_id, &bob, &alice, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_in, &alice);
+    client.complete_transfer(&transfer_in, &bob);
+
+    let history = client.get_transfer_history_for_address(&alice);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().certificate_id, sent_id);
+    assert_eq!(history.get(0).unwrap().from_address, alice);
+    assert_eq!(history.get(1).unwrap().certificate_id, received_id);
+    assert_eq!(history.get(1).unwrap().to_address, alice);
+
+    // An address with no activity has an empty history
+    assert_eq!(client.get_transfer_history_for_address(&issuer).len(), 0);
+}
+
+#[test]
+fn test_can_transfer_dry_run() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmDryRun");
+    let cert_id = String::from_str(&env, "cert-dry-run");
+    let revoked_id = String::from_str(&env, "cert-dry-revoked");
+    let frozen_id = String::from_str(&env, "cert-dry-frozen");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&revoked_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&frozen_id, &issuer, &owner, &metadata_uri, &None);
+    client.revoke_certificate(&revoked_id, &String::from_str(&env, "Revoked"));
+    client.freeze_certificate(&frozen_id, &issuer, &String::from_str(&env, "Dispute"), &7);
+
+    // Success case
+    assert_eq!(client.try_can_transfer(&cert_id, &owner, &new_owner), Ok(Ok(())));
+
+    // Missing certificate
+    let result = client.try_can_transfer(&String::from_str(&env, "missing-cert"), &owner, &new_owner);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+
+    // Not the owner
+    let result = client.try_can_transfer(&cert_id, &new_owner, &issuer);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    // Revoked
+    let result = client.try_can_transfer(&revoked_id, &owner, &new_owner);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+
+    // Frozen
+    let result = client.try_can_transfer(&frozen_id, &owner, &new_owner);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateFrozen)));
+
+    // Self-transfer
+    let result = client.try_can_transfer(&cert_id, &owner, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::SelfTransferNotAllowed)));
+
+    // Another transfer already in flight
+    let transfer_id = String::from_str(&env, "transfer-dry-run");
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    let result = client.try_can_transfer(&cert_id, &owner, &issuer);
+    assert_eq!(result, Err(Ok(CertificateError::TransferAlreadyActive)));
+
+    // The dry run did not create any state of its own
+    assert_eq!(client.get_transfer_count(), 1);
+
+    // Once the transfer is cancelled the certificate is transferable again
+    client.cancel_transfer(&transfer_id, &owner);
+    assert_eq!(client.try_can_transfer(&cert_id, &owner, &new_owner), Ok(Ok(())));
+}
+
+#[test]
+fn test_revoked_index_export() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmCrl");
+    let id1 = String::from_str(&env, "cert-crl-1");
+    let id2 = String::from_str(&env, "cert-crl-2");
+    let id3 = String::from_str(&env, "cert-crl-3");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&id1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id2, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id3, &issuer, &owner, &metadata_uri, &None);
+
+    assert_eq!(client.get_revoked_count(), 0);
+
+    let reason = String::from_str(&env, "Revoked");
+    client.revoke_certificate(&id1, &reason);
+    client.revoke_certificate(&id3, &reason);
+
+    assert_eq!(client.get_revoked_count(), 2);
+    let revoked = client.get_revoked_certificates(&0, &10);
+    assert_eq!(revoked.len(), 2);
+    assert_eq!(revoked.get(0).unwrap(), id1);
+    assert_eq!(revoked.get(1).unwrap(), id3);
+
+    // Paging past the first entry
+    let page = client.get_revoked_certificates(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap(), id3);
+
+    // Out-of-range pages are empty
+    assert_eq!(client.get_revoked_certificates(&5, &10).len(), 0);
+}
+
+#[test]
+fn test_transfer_issuer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let new_issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-issuer");
+    let metadata_uri = String::from_str(&env, "ipfs://QmIssuer");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    client.transfer_issuer(&cert_id, &new_issuer);
+    assert_eq!(env.auths()[0].0, issuer);
+    assert_eq!(client.get_certificate(&cert_id).issuer, new_issuer);
+
+    // Handing control to the current issuer is rejected
+    let result = client.try_transfer_issuer(&cert_id, &new_issuer);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Revocation now requires the new issuer's authorization, not the old one's
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Program handed off"));
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, new_issuer);
+    assert!(client.is_revoked(&cert_id));
+}
+
+#[test]
+fn test_admin_force_complete_and_cancel() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let stuck_id = String::from_str(&env, "cert-stuck");
+    let pending_id = String::from_str(&env, "cert-pending");
+    let metadata_uri = String::from_str(&env, "ipfs://QmForce");
+
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    client.issue_certificate(&stuck_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&pending_id, &issuer, &owner, &metadata_uri, &None);
+
+    // An accepted transfer that nobody completes
+    let stuck_transfer = String::from_str(&env, "transfer-stuck");
+    client.initiate_transfer(&stuck_transfer, &stuck_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&stuck_transfer, &new_owner);
+
+    client.admin_force_complete(&stuck_transfer);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_transfer(&stuck_transfer).status, TransferStatus::Completed);
+    assert_eq!(client.get_certificate(&stuck_id).owner, new_owner);
+    assert_eq!(client.get_transfer_history(&stuck_id).len(), 1);
+
+    // A pending transfer can be cancelled by the admin
+    let pending_transfer = String::from_str(&env, "transfer-pending");
+    client.initiate_transfer(&pending_transfer, &pending_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    // Pending transfers cannot be force-completed
+    let result = client.try_admin_force_complete(&pending_transfer);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidTransferStatus)));
+
+    client.admin_force_cancel(&pending_transfer);
+    assert_eq!(client.get_transfer(&pending_transfer).status, TransferStatus::Cancelled);
+    assert_eq!(client.get_pending_transfers(&new_owner).len(), 0);
+    assert_eq!(client.get_certificate(&pending_id).owner, owner);
+
+    // The certificate is free to be transferred again
+    assert_eq!(client.try_can_transfer(&pending_id, &owner, &new_owner), Ok(Ok(())));
+}
+
+#[test]
+fn test_max_certs_per_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmLimit");
+    let id1 = String::from_str(&env, "cert-limit-1");
+    let id2 = String::from_str(&env, "cert-limit-2");
+    let id3 = String::from_str(&env, "cert-limit-3");
+
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    assert_eq!(client.get_max_certs_per_owner(), 0);
+    client.set_max_certs_per_owner(&1);
+
+    client.issue_certificate(&id1, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 1);
+
+    // A second active certificate exceeds the limit
+    let result = client.try_issue_certificate(&id2, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::OwnerLimitReached)));
+
+    // Transfers into a full address are blocked at completion
+    client.issue_certificate(&id3, &issuer, &other, &metadata_uri, &None);
+    let transfer_id = String::from_str(&env, "transfer-limit");
+    client.initiate_transfer(&transfer_id, &id3, &other, &owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_id, &owner);
+    let result = client.try_complete_transfer(&transfer_id, &other);
+    assert_eq!(result, Err(Ok(CertificateError::OwnerLimitReached)));
+
+    // Revoking the held certificate frees the slot
+    client.revoke_certificate(&id1, &String::from_str(&env, "License replaced"));
+    client.issue_certificate(&id2, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 2);
+}
+
+#[test]
+fn test_suspend_and_reinstate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-suspend");
+    let metadata_uri = String::from_str(&env, "ipfs://QmSuspend");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    client.suspend_certificate(&cert_id, &String::from_str(&env, "Pending review"));
+    assert_eq!(client.verify_certificate(&cert_id).status, CertificateStatus::Suspended);
+
+    // Suspended is not revoked
+    assert!(!client.is_revoked(&cert_id));
+
+    // Transfers are blocked while suspended
+    let result = client.try_can_transfer(&cert_id, &owner, &new_owner);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateSuspended)));
+
+    client.reinstate_from_suspension(&cert_id);
+    assert_eq!(client.verify_certificate(&cert_id).status, CertificateStatus::Valid);
+
+    // Reinstating twice is rejected
+    let result = client.try_reinstate_from_suspension(&cert_id);
+    assert_eq!(result, Err(Ok(CertificateError::NotSuspended)));
+
+    // Transfers work again
+    let transfer_id = String::from_str(&env, "transfer-suspend");
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer(&transfer_id).status, TransferStatus::Pending);
+}
+
+#[test]
+fn test_event_topics_include_certificate_id() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-topics");
+    let transfer_id = String::from_str(&env, "transfer-topics");
+    let metadata_uri = String::from_str(&env, "ipfs://QmTopics");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("cert_iss"), cert_id.clone()).into_val(&env));
+
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("transfer_init"), cert_id.clone()).into_val(&env));
+
+    client.accept_transfer(&transfer_id, &new_owner);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("transfer_accept"), cert_id.clone()).into_val(&env));
+
+    client.complete_transfer(&transfer_id, &owner);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("transfer_complete"), cert_id.clone()).into_val(&env));
+
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Revoked"));
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("cert_rev"), cert_id.clone()).into_val(&env));
+}
+
+#[test]
+fn test_get_active_certificates_by_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmActive");
+    let valid_id = String::from_str(&env, "cert-active-valid");
+    let revoked_id = String::from_str(&env, "cert-active-revoked");
+    let expired_id = String::from_str(&env, "cert-active-expired");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.issue_certificate(&valid_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&revoked_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate_with_expiry(&expired_id, &issuer, &owner, &metadata_uri, &2_000, &false);
+    client.revoke_certificate(&revoked_id, &String::from_str(&env, "Revoked"));
+
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+
+    // All three are still indexed against the owner
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 3);
+
+    // Only the valid one is active
+    let active = client.get_active_certificates_by_owner(&owner);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap(), valid_id);
+}
+
+#[test]
+fn test_update_metadata_uri_checked() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-nonce");
+    let metadata_uri = String::from_str(&env, "ipfs://QmNonce");
+    let new_uri = String::from_str(&env, "ipfs://QmNonceV2");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    let nonce = client.get_certificate(&cert_id).nonce;
+    assert_eq!(nonce, 0);
+
+    // Update with the current nonce succeeds and bumps it
+    client.update_metadata_uri_checked(&cert_id, &new_uri, &nonce, &None);
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.metadata_uri, new_uri);
+    assert_eq!(cert.nonce, 1);
+
+    // A writer holding the old nonce is rejected
+    let result = client.try_update_metadata_uri_checked(
+        &cert_id,
+        &String::from_str(&env, "ipfs://QmStale"),
+        &nonce,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::VersionMismatch)));
+    assert_eq!(client.get_certificate(&cert_id).metadata_uri, new_uri);
+
+    // Other mutations also bump the nonce
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Revoked"));
+    assert_eq!(client.get_certificate(&cert_id).nonce, 2);
+}
+
+#[contract]
+pub struct MockCertificateReceiver;
+
+#[contractimpl]
+impl MockCertificateReceiver {
+    pub fn on_certificate_received(
+        env: Env,
+        _from: Address,
+        certificate_id: String,
+        _transfer_id: String,
+    ) -> bool {
+        env.storage().instance().set(&symbol_short!("received"), &certificate_id);
+        true
+    }
+
+    pub fn last_received(env: Env) -> Option<String> {
+        env.storage().instance().get(&symbol_short!("received"))
+    }
+}
+
+#[contract]
+pub struct RejectingCertificateReceiver;
+
+#[contractimpl]
+impl RejectingCertificateReceiver {
+    pub fn on_certificate_received(
+        _env: Env,
+        _from: Address,
+        _certificate_id: String,
+        _transfer_id: String,
+    ) -> bool {
+        false
+    }
+}
+
+#[test]
+fn test_transfer_to_contract_with_callback() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let receiver_id = env.register_contract(None, MockCertificateReceiver);
+    let receiver = MockCertificateReceiverClient::new(&env, &receiver_id);
+    let rejecting_id = env.register_contract(None, RejectingCertificateReceiver);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-callback");
+    let metadata_uri = String::from_str(&env, "ipfs://QmCallback");
+
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    // A rejecting receiver reverts the transfer
+    let rejected_transfer = String::from_str(&env, "transfer-callback-rejected");
+    client.initiate_transfer(&rejected_transfer, &cert_id, &owner, &rejecting_id, &false, &0u64, &None, &true, &None);
+    client.accept_transfer(&rejected_transfer, &rejecting_id);
+    let result = client.try_complete_transfer(&rejected_transfer, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::RecipientRejected)));
+    assert_eq!(client.get_certificate(&cert_id).owner, owner);
+    client.admin_force_cancel(&rejected_transfer);
+
+    // An accepting receiver is notified and takes ownership
+    let transfer_id = String::from_str(&env, "transfer-callback");
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &receiver_id, &false, &0u64, &None, &true, &None);
+    client.accept_transfer(&transfer_id, &receiver_id);
+    client.complete_transfer(&transfer_id, &owner);
+
+    assert_eq!(client.get_certificate(&cert_id).owner, receiver_id);
+    assert_eq!(receiver.last_received(), Some(cert_id));
+}
+
+#[test]
+fn test_reclaim_expired() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-reclaim");
+    let metadata_uri = String::from_str(&env, "ipfs://QmReclaim");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.issue_certificate_with_expiry(&cert_id, &issuer, &owner, &metadata_uri, &2_000, &false);
+
+    // Not yet expired
+    let result = client.try_reclaim_expired(&cert_id);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    assert_eq!(client.get_certificate(&cert_id).owner, owner);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_500);
+
+    client.reclaim_expired(&cert_id);
+    assert_eq!(env.auths()[0].0, issuer);
+    assert_eq!(client.get_certificate(&cert_id).owner, issuer);
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 0);
+    assert_eq!(client.get_certificates_by_owner(&issuer).len(), 1);
+}
+
+#[test]
+fn test_transfer_status_and_timestamp_projections() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-projection");
+    let transfer_id = String::from_str(&env, "transfer-projection");
+    let metadata_uri = String::from_str(&env, "ipfs://QmProjection");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100);
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    let full = client.get_transfer(&transfer_id);
+    assert_eq!(client.get_transfer_status(&transfer_id), full.status);
+    assert_eq!(client.get_transfer_timestamps(&transfer_id), (100, None, None));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.accept_transfer(&transfer_id, &new_owner);
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Accepted);
+    assert_eq!(client.get_transfer_timestamps(&transfer_id), (100, Some(200), None));
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.complete_transfer(&transfer_id, &owner);
+    let full = client.get_transfer(&transfer_id);
+    assert_eq!(client.get_transfer_status(&transfer_id), full.status);
+    assert_eq!(
+        client.get_transfer_timestamps(&transfer_id),
+        (full.initiated_at, full.accepted_at, full.completed_at)
+    );
+    assert_eq!(full.completed_at, Some(300));
+
+    let result = client.try_get_transfer_status(&String::from_str(&env, "missing-transfer"));
+    assert_eq!(result, Err(Ok(CertificateError::TransferNotFound)));
+}
+
+#[test]
+fn test_allow_self_transfer_flag() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-custody");
+    let transfer_id = String::from_str(&env, "transfer-custody");
+    let metadata_uri = String::from_str(&env, "ipfs://QmCustody");
+
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    // Flag off by default: self-transfer is rejected
+    assert!(!client.is_self_transfer_allowed());
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::SelfTransferNotAllowed)));
+
+    // Flag on: the full ceremony runs
+    client.set_allow_self_transfer(&true);
+    assert!(client.is_self_transfer_allowed());
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_id, &owner);
+    client.complete_transfer(&transfer_id, &owner);
+
+    assert_eq!(client.get_transfer(&transfer_id).status, TransferStatus::Completed);
+    assert_eq!(client.get_certificate(&cert_id).owner, owner);
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 1);
+}
+
+#[test]
+fn test_batch_update_metadata() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let other_issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let old_uri = String::from_str(&env, "https://old-gateway/ipfs/Qm");
+    let new_uri = String::from_str(&env, "https://new-gateway/ipfs/Qm");
+    let id1 = String::from_str(&env, "cert-batch-1");
+    let id2 = String::from_str(&env, "cert-batch-2");
+    let foreign_id = String::from_str(&env, "cert-batch-foreign");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&id1, &issuer, &owner, &old_uri, &None);
+    client.issue_certificate(&id2, &issuer, &owner, &old_uri, &None);
+    client.issue_certificate(&foreign_id, &other_issuer, &owner, &old_uri, &None);
+
+    // Clean bulk update
+    let mut updates = Vec::new(&env);
+    updates.push_back((id1.clone(), new_uri.clone()));
+    updates.push_back((id2.clone(), new_uri.clone()));
+    client.batch_update_metadata(&issuer, &updates);
+    assert_eq!(client.get_certificate(&id1).metadata_uri, new_uri);
+    assert_eq!(client.get_certificate(&id2).metadata_uri, new_uri);
+
+    // A foreign certificate fails the whole batch
+    let newer_uri = String::from_str(&env, "https://newer-gateway/ipfs/Qm");
+    let mut updates = Vec::new(&env);
+    updates.push_back((id1.clone(), newer_uri.clone()));
+    updates.push_back((foreign_id.clone(), newer_uri.clone()));
+    let result = client.try_batch_update_metadata(&issuer, &updates);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    assert_eq!(client.get_certificate(&id1).metadata_uri, new_uri);
+    assert_eq!(client.get_certificate(&foreign_id).metadata_uri, old_uri);
+}
+
+#[test]
+fn test_certificates_issued_between() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmRange");
+    let early = String::from_str(&env, "cert-range-early");
+    let middle = String::from_str(&env, "cert-range-middle");
+    let late = String::from_str(&env, "cert-range-late");
+
+    env.mock_all_auths();
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.issue_certificate(&early, &issuer, &owner, &metadata_uri, &None);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.issue_certificate(&middle, &issuer, &owner, &metadata_uri, &None);
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    client.issue_certificate(&late, &issuer, &owner, &metadata_uri, &None);
+
+    // Sub-window picks out only the middle certificate
+    let ids = client.get_certificates_issued_between(&150, &250, &10);
+    assert_eq!(ids.len(), 1);
+    assert_eq!(ids.get(0).unwrap(), middle);
+
+    // Inclusive bounds
+    let ids = client.get_certificates_issued_between(&200, &300, &10);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids.get(1).unwrap(), late);
+
+    // Limit caps the result
+    let ids = client.get_certificates_issued_between(&0, &1_000, &2);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids.get(0).unwrap(), early);
+}
+
+#[test]
+fn test_purge_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-purge");
+    let transfer_id = String::from_str(&env, "transfer-purge");
+    let metadata_uri = String::from_str(&env, "ipfs://QmPurge");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    // Active transfers cannot be purged
+    let result = client.try_purge_transfer(&transfer_id, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidTransferStatus)));
+
+    client.accept_transfer(&transfer_id, &new_owner);
+    client.complete_transfer(&transfer_id, &owner);
+
+    // Unrelated addresses cannot purge
+    let result = client.try_purge_transfer(&transfer_id, &stranger);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    client.purge_transfer(&transfer_id, &new_owner);
+
+    let result = client.try_get_transfer(&transfer_id);
+    assert_eq!(result, Err(Ok(CertificateError::TransferNotFound)));
+
+    // History is untouched
+    let history = client.get_transfer_history(&cert_id);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().transfer_id, transfer_id);
+}
+
+#[test]
+fn test_purge_transfer_leaves_tombstone() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-purge-tombstone");
+    let transfer_id = String::from_str(&env, "transfer-purge-tombstone");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmTombstone"), &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.cancel_transfer(&transfer_id, &owner);
+
+    // Simulate a transfer created before ids were reserved at initiation
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .remove(&DataKey::Transfer(TransferKey::UsedTransferId(transfer_id.clone())));
+    });
+
+    client.purge_transfer(&transfer_id, &owner);
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+}
+
+fn sign_claim_payload(
+    client: &CertificateContractClient,
+    signing_key: &ed25519_dalek::SigningKey,
+    id: &String,
+    issuer: &Address,
+    owner: &Address,
+    metadata_uri: &String,
+    issued_at: u64,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = &client.env;
+    let payload = (client.address.clone(), id.clone(), issuer.clone(), owner.clone(), metadata_uri.clone(), issued_at)
+        .to_xdr(env);
+    let mut message = [0u8; 512];
+    let len = payload.len() as usize;
+    payload.copy_into_slice(&mut message[..len]);
+    BytesN::from_array(env, &signing_key.sign(&message[..len]).to_bytes())
+}
+
+#[test]
+fn test_claim_signed_certificate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-claim");
+    let metadata_uri = String::from_str(&env, "ipfs://QmClaim");
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    // Without a registered key nothing can be verified
+    let signature = sign_claim_payload(&client, &signing_key, &cert_id, &issuer, &owner, &metadata_uri, 900);
+    let result = client.try_claim_certificate(&cert_id, &issuer, &owner, &metadata_uri, &signature, &900);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidSignature)));
+
+    client.register_issuer_key(&issuer, &public_key);
+    assert_eq!(client.get_issuer_key(&issuer), Some(public_key));
+
+    // A tampered payload fails signature verification
+    let tampered_uri = String::from_str(&env, "ipfs://QmTampered");
+    let result = client.try_claim_certificate(&cert_id, &issuer, &owner, &tampered_uri, &signature, &900);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidSignature)));
+
+    // The signature is bound to the issuer it was made for
+    let other_issuer = Address::generate(&env);
+    client.register_issuer_key(&other_issuer, &public_key);
+    let result = client.try_claim_certificate(&cert_id, &other_issuer, &owner, &metadata_uri, &signature, &900);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidSignature)));
+
+    // Signatures older than the validity window are refused
+    let stale_id = String::from_str(&env, "cert-claim-stale");
+    let stale_at = 1_000 + 7 * 24 * 60 * 60;
+    let stale_signature = sign_claim_payload(&client, &signing_key, &stale_id, &issuer, &owner, &metadata_uri, 900);
+    env.ledger().with_mut(|li| li.timestamp = stale_at);
+    let result = client.try_claim_certificate(&stale_id, &issuer, &owner, &metadata_uri, &stale_signature, &900);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidSignature)));
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    // The genuine payload is accepted
+    client.claim_certificate(&cert_id, &issuer, &owner, &metadata_uri, &signature, &900);
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.issuer, issuer);
+    assert_eq!(cert.owner, owner);
+    assert_eq!(cert.metadata_uri, metadata_uri);
+
+    // The same payload cannot be claimed twice
+    let result = client.try_claim_certificate(&cert_id, &issuer, &owner, &metadata_uri, &signature, &900);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+}
+
+#[test]
+fn test_transfer_fee_royalty_split() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-royalty");
+    let transfer_id = String::from_str(&env, "transfer-royalty");
+    let metadata_uri = String::from_str(&env, "ipfs://QmRoyalty");
+
+    env.mock_all_auths();
+
+    let fee_token = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &fee_token.address());
+    let token = soroban_sdk::token::Client::new(&env, &fee_token.address());
+    token_admin.mint(&new_owner, &10_000);
+
+    client.initialize(&admin);
+
+    // Royalty above 100% is rejected
+    let result = client.try_set_fee_config(&fee_token.address(), &treasury, &10_001);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    client.set_fee_config(&fee_token.address(), &treasury, &500);
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &1_000u64, &None, &false, &None);
+    client.accept_transfer(&transfer_id, &new_owner);
+    client.complete_transfer(&transfer_id, &owner);
+
+    // 5% to the issuer, the rest to the treasury
+    assert_eq!(token.balance(&issuer), 50);
+    assert_eq!(token.balance(&treasury), 950);
+    assert_eq!(token.balance(&new_owner), 9_000);
+}
+
+#[test]
+fn test_revoke_all_by_issuer_in_pages() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let other_issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmCompromise");
+    let ids = [
+        String::from_str(&env, "cert-compromise-1"),
+        String::from_str(&env, "cert-compromise-2"),
+        String::from_str(&env, "cert-compromise-3"),
+        String::from_str(&env, "cert-compromise-4"),
+    ];
+    let unrelated_id = String::from_str(&env, "cert-unrelated");
+
+    env.mock_all_auths();
+
+    for id in ids.iter() {
+        client.issue_certificate(id, &issuer, &owner, &metadata_uri, &None);
+    }
+    client.issue_certificate(&unrelated_id, &other_issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_certificates_by_issuer(&issuer).len(), 4);
+
+    let reason = String::from_str(&env, "Issuer key compromised");
+    assert_eq!(client.revoke_all_by_issuer(&issuer, &reason, &0, &2), 2);
+    assert_eq!(client.revoke_all_by_issuer(&issuer, &reason, &2, &2), 2);
+
+    for id in ids.iter() {
+        assert!(client.is_revoked(id));
+    }
+    assert!(!client.is_revoked(&unrelated_id));
+
+    // Nothing left to revoke
+    assert_eq!(client.revoke_all_by_issuer(&issuer, &reason, &0, &10), 0);
+}
+
+#[test]
+fn test_can_revoke_with_delegates() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-delegate");
+    let metadata_uri = String::from_str(&env, "ipfs://QmDelegate");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.add_revocation_delegate(&issuer, &delegate);
+
+    assert!(client.can_revoke(&cert_id, &issuer));
+    assert!(client.can_revoke(&cert_id, &delegate));
+    assert!(!client.can_revoke(&cert_id, &stranger));
+
+    let result = client.try_can_revoke(&String::from_str(&env, "missing-cert"), &issuer);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+
+    // A stranger cannot revoke as a delegate
+    let reason = String::from_str(&env, "Delegated revocation");
+    let result = client.try_delegate_revoke_certificate(&cert_id, &stranger, &reason);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    client.delegate_revoke_certificate(&cert_id, &delegate, &reason);
+    assert!(client.is_revoked(&cert_id));
+    assert_eq!(client.get_certificate(&cert_id).revoked_by, Some(delegate.clone()));
+
+    // Removed delegates lose the right
+    client.remove_revocation_delegate(&issuer, &delegate);
+    assert!(!client.can_revoke(&cert_id, &delegate));
+}
+
+#[test]
+fn test_transfer_completion_deadline() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let on_time_id = String::from_str(&env, "cert-deadline-ok");
+    let late_id = String::from_str(&env, "cert-deadline-late");
+    let on_time_transfer = String::from_str(&env, "transfer-deadline-ok");
+    let late_transfer = String::from_str(&env, "transfer-deadline-late");
+    let metadata_uri = String::from_str(&env, "ipfs://QmDeadline");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.issue_certificate(&on_time_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&late_id, &issuer, &owner, &metadata_uri, &None);
+
+    let options = Some(TransferOptions { complete_by: Some(2_000), ttl_seconds: 0, data: None, prepay_fee: false });
+
+    // The deadline must lie in the future
+    let past = Some(TransferOptions { complete_by: Some(1_000), ttl_seconds: 0, data: None, prepay_fee: false });
+    let result = client.try_initiate_transfer_v2(&on_time_transfer, &on_time_id, &owner, &new_owner, &false, &0i128, &None, &false, &None, &past);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    client.initiate_transfer_v2(&on_time_transfer, &on_time_id, &owner, &new_owner, &false, &0i128, &None, &false, &None, &options);
+    client.initiate_transfer_v2(&late_transfer, &late_id, &owner, &new_owner, &false, &0i128, &None, &false, &None, &options);
+    assert_eq!(client.get_transfer(&late_transfer).complete_by, Some(2_000));
+
+    client.accept_transfer(&on_time_transfer, &new_owner);
+    client.accept_transfer(&late_transfer, &new_owner);
+
+    // Before the deadline
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    client.complete_transfer(&on_time_transfer, &owner);
+    assert_eq!(client.get_certificate(&on_time_id).owner, new_owner);
+
+    // After the deadline, even though accepted
+    env.ledger().with_mut(|li| li.timestamp = 2_001);
+    let result = client.try_complete_transfer(&late_transfer, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::TransferDeadlinePassed)));
+    assert_eq!(client.get_certificate(&late_id).owner, owner);
+}
+
+#[test]
+fn test_verify_metadata_hash() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-hash");
+    let metadata_uri = String::from_str(&env, "ipfs://QmHash");
+    let content_hash: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"{\"name\":\"Diploma\"}")).into();
+    let swapped_hash: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"{\"name\":\"Forgery\"}")).into();
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &Some(content_hash.clone()));
+
+    assert!(client.verify_metadata(&cert_id, &content_hash));
+    assert!(!client.verify_metadata(&cert_id, &swapped_hash));
+
+    // Updating the URI records the new content hash
+    let nonce = client.get_certificate(&cert_id).nonce;
+    client.update_metadata_uri_checked(
+        &cert_id,
+        &String::from_str(&env, "ipfs://QmHashV2"),
+        &nonce,
+        &Some(swapped_hash.clone()),
+    );
+    assert!(client.verify_metadata(&cert_id, &swapped_hash));
+    assert!(!client.verify_metadata(&cert_id, &content_hash));
+}
+
+#[test]
+fn test_preapproved_incoming_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-preapproved");
+    let transfer_id = String::from_str(&env, "transfer-preapproved");
+    let metadata_uri = String::from_str(&env, "ipfs://QmPreapproved");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.preapprove_incoming(&new_owner, &owner);
+    assert!(client.is_preapproved(&new_owner, &owner));
+
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    // Accepted on initiation and never listed as pending
+    let transfer = client.get_transfer(&transfer_id);
+    assert_eq!(transfer.status, TransferStatus::Accepted);
+    assert!(transfer.accepted_at.is_some());
+    assert_eq!(client.get_pending_transfers(&new_owner).len(), 0);
+
+    // Completes without a manual accept
+    client.complete_transfer(&transfer_id, &owner);
+    assert_eq!(client.get_certificate(&cert_id).owner, new_owner);
+
+    // Withdrawn pre-approval restores the manual flow
+    client.revoke_preapproval(&new_owner, &owner);
+    assert!(!client.is_preapproved(&new_owner, &owner));
+}
+
+#[test]
+fn test_completer_policies() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmPolicy");
+
+    env.mock_all_auths();
+
+    // (policy, disallowed completer, allowed completer)
+    let cases = [
+        (CompleterPolicy::AnyParty, stranger.clone(), issuer.clone()),
+        (CompleterPolicy::RecipientOnly, owner.clone(), new_owner.clone()),
+        (CompleterPolicy::SenderOnly, new_owner.clone(), owner.clone()),
+        (CompleterPolicy::IssuerOnly, owner.clone(), issuer.clone()),
+    ];
+
+    for (i, (policy, disallowed, allowed)) in cases.iter().enumerate() {
+        let cert_id = String::from_str(&env, &format!("cert-policy-{}", i));
+        let transfer_id = String::from_str(&env, &format!("transfer-policy-{}", i));
+
+        client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+        client.initiate_transfer(
+            &transfer_id,
+            &cert_id,
+            &owner,
+            &new_owner,
+            &false,
+            &0u64,
+            &None,
+            &false,
+            &Some(policy.clone()),
+        );
+        assert_eq!(client.get_transfer(&transfer_id).completer_policy, *policy);
+        client.accept_transfer(&transfer_id, &new_owner);
+
+        let result = client.try_complete_transfer(&transfer_id, disallowed);
+        assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+        client.complete_transfer(&transfer_id, allowed);
+        assert_eq!(client.get_certificate(&cert_id).owner, new_owner);
+    }
+}
+
+#[test]
+fn test_last_modified_at_tracks_latest_change() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-modified");
+
+    env.mock_all_auths();
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.issue_certificate(
+        &cert_id,
+        &issuer,
+        &owner,
+        &String::from_str(&env, "ipfs://QmModified"),
+        &None,
+    );
+    assert_eq!(client.get_certificate(&cert_id).last_modified_at, 1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.suspend_certificate(&cert_id, &String::from_str(&env, "Audit"));
+    assert_eq!(client.get_certificate(&cert_id).last_modified_at, 2_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    client.reinstate_from_suspension(&cert_id);
+    assert_eq!(client.get_certificate(&cert_id).last_modified_at, 3_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 4_000);
+    let nonce = client.get_certificate(&cert_id).nonce;
+    client.update_metadata_uri_checked(
+        &cert_id,
+        &String::from_str(&env, "ipfs://QmModifiedV2"),
+        &nonce,
+        &None,
+    );
+    assert_eq!(client.get_certificate(&cert_id).last_modified_at, 4_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Expired credential"));
+
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.last_modified_at, 5_000);
+    assert_eq!(cert.issued_at, 1_000);
+}
+
+#[test]
+fn test_transfer_exists() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-exists");
+    let transfer_id = String::from_str(&env, "transfer-exists");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmExists"), &None);
+    assert!(!client.transfer_exists(&transfer_id));
+
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    assert!(client.transfer_exists(&transfer_id));
+    assert!(!client.transfer_exists(&String::from_str(&env, "transfer-unknown")));
+}
+
+#[test]
+fn test_certificate_prerequisite_chain() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let basic_id = String::from_str(&env, "license-basic");
+    let advanced_id = String::from_str(&env, "license-advanced");
+    let metadata_uri = String::from_str(&env, "ipfs://QmLicense");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&basic_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&advanced_id, &issuer, &owner, &metadata_uri, &None);
+
+    client.link_certificate(&advanced_id, &basic_id);
+    // Linking twice does not duplicate the entry
+    client.link_certificate(&advanced_id, &basic_id);
+
+    let prerequisites = client.get_prerequisites(&advanced_id);
+    assert_eq!(prerequisites.len(), 1);
+    assert_eq!(prerequisites.get(0).unwrap(), basic_id);
+    assert!(client.verify_with_prerequisites(&advanced_id));
+
+    // Revoking the prerequisite breaks the chain
+    client.revoke_certificate(&basic_id, &String::from_str(&env, "Lapsed"));
+    assert!(!client.verify_with_prerequisites(&advanced_id));
+
+    let result = client.try_link_certificate(&advanced_id, &String::from_str(&env, "license-missing"));
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+}
+
+#[test]
+fn test_get_contract_stats() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmStats");
+
+    env.mock_all_auths();
+
+    let cert_1 = String::from_str(&env, "cert-stats-1");
+    let cert_2 = String::from_str(&env, "cert-stats-2");
+    let cert_3 = String::from_str(&env, "cert-stats-3");
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_3, &issuer, &owner, &metadata_uri, &None);
+
+    client.revoke_certificate(&cert_3, &String::from_str(&env, "Issued in error"));
+
+    // One transfer runs to completion, one is left pending
+    let transfer_1 = String::from_str(&env, "transfer-stats-1");
+    client.initiate_transfer(&transfer_1, &cert_1, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_1, &new_owner);
+    client.complete_transfer(&transfer_1, &new_owner);
+
+    let transfer_2 = String::from_str(&env, "transfer-stats-2");
+    client.initiate_transfer(&transfer_2, &cert_2, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    let stats = client.get_contract_stats();
+    assert_eq!(stats.total_certificates, 3);
+    assert_eq!(stats.total_revoked, 1);
+    assert_eq!(stats.total_transfers, 2);
+    assert_eq!(stats.pending_transfers, 1);
+}
+
+#[test]
+fn test_two_step_admin_handover() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    // A non-proposed address cannot accept
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &stranger,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "accept_admin",
+                args: ().into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_accept_admin();
+    assert!(result.is_err());
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    // The proposed admin accepts
+    client
+        .mock_auths(&[MockAuth {
+            address: &new_admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "accept_admin",
+                args: ().into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .accept_admin();
+    assert_eq!(client.get_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_pending_admin(), None);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("admin_chg"),).into_val(&env));
+    let event_data: AdminChangedEvent = data.into_val(&env);
+    assert_eq!(event_data.old_admin, admin);
+    assert_eq!(event_data.new_admin, new_admin);
+
+    // The old admin no longer holds admin privileges
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "set_max_certs_per_owner",
+                args: (5u32,).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_set_max_certs_per_owner(&5u32);
+    assert!(result.is_err());
+    assert_eq!(client.get_max_certs_per_owner(), 0);
+}
+
+#[test]
+fn test_scheduled_revocation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-scheduled");
+    let other_id = String::from_str(&env, "cert-scheduled-cancelled");
+    let metadata_uri = String::from_str(&env, "ipfs://QmScheduled");
+    let reason = String::from_str(&env, "License term ended");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&other_id, &issuer, &owner, &metadata_uri, &None);
+
+    client.schedule_revocation(&cert_id, &5_000u64, &reason);
+    client.schedule_revocation(&other_id, &5_000u64, &reason);
+    client.cancel_scheduled_revocation(&other_id);
+
+    // Valid before the effective date
+    env.ledger().with_mut(|li| li.timestamp = 4_999);
+    assert!(!client.is_revoked(&cert_id));
+    assert_eq!(client.verify_certificate(&cert_id).status, CertificateStatus::Valid);
+
+    // Revoked once the effective date is reached
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    assert!(client.is_revoked(&cert_id));
+    let verification = client.verify_certificate(&cert_id);
+    assert_eq!(verification.status, CertificateStatus::Revoked);
+    assert_eq!(verification.revocation_reason, Some(RevocationReason::Other(reason)));
+
+    let result = client.try_cancel_scheduled_revocation(&cert_id);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+
+    // The cancelled schedule never takes effect
+    assert!(!client.is_revoked(&other_id));
+}
+
+#[test]
+fn test_get_pending_transfer_count() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmPendingCount");
+
+    env.mock_all_auths();
+
+    let cert_1 = String::from_str(&env, "cert-pending-1");
+    let cert_2 = String::from_str(&env, "cert-pending-2");
+    let transfer_1 = String::from_str(&env, "transfer-pending-1");
+    let transfer_2 = String::from_str(&env, "transfer-pending-2");
+
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_pending_transfer_count(&recipient), 0);
+
+    client.initiate_transfer(&transfer_1, &cert_1, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.initiate_transfer(&transfer_2, &cert_2, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_pending_transfer_count(&recipient), 2);
+
+    client.accept_transfer(&transfer_1, &recipient);
+    assert_eq!(client.get_pending_transfer_count(&recipient), 1);
+}
+
+#[test]
+fn test_transfer_fee_floor_and_cap() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-fee-range");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_set_transfer_fee_limits(&-1i128, &1_000i128),
+        Err(Ok(CertificateError::InvalidData))
+    );
+    client.set_transfer_fee_limits(&100i128, &1_000i128);
+    assert_eq!(client.get_transfer_fee_limits(), (100i128, 1_000i128));
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmFeeRange"), &None);
+
+    // Below the floor
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, "transfer-fee-low"),
+        &cert_id, &owner, &new_owner, &false, &99u64, &None, &false, &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Above the cap
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, "transfer-fee-high"),
+        &cert_id, &owner, &new_owner, &false, &1_001u64, &None, &false, &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Within range
+    let transfer_id = String::from_str(&env, "transfer-fee-ok");
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &500u64, &None, &false, &None);
+    assert_eq!(client.get_transfer(&transfer_id).transfer_fee, 500i128);
+}
+
+#[test]
+fn test_owner_at_timestamp() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let first_owner = Address::generate(&env);
+    let second_owner = Address::generate(&env);
+    let third_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-owner-at");
+
+    env.mock_all_auths();
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.issue_certificate(&cert_id, &issuer, &first_owner, &String::from_str(&env, "ipfs://QmOwnerAt"), &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.direct_transfer(&cert_id, &first_owner, &second_owner, &false);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    client.direct_transfer(&cert_id, &second_owner, &third_owner, &false);
+
+    assert_eq!(client.owner_at(&cert_id, &1_500u64), first_owner);
+    assert_eq!(client.owner_at(&cert_id, &2_000u64), second_owner);
+    assert_eq!(client.owner_at(&cert_id, &2_500u64), second_owner);
+    assert_eq!(client.owner_at(&cert_id, &3_500u64), third_owner);
+
+    let result = client.try_owner_at(&cert_id, &999u64);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    let result = client.try_owner_at(&String::from_str(&env, "cert-missing"), &1_500u64);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+}
+
+#[test]
+fn test_denylisted_recipient() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let blocked = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-denylist");
+    let metadata_uri = String::from_str(&env, "ipfs://QmDenylist");
+    let transfer_id = String::from_str(&env, "transfer-denylist");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.add_to_denylist(&blocked);
+    assert!(client.is_denylisted(&blocked));
+
+    // Cannot be issued a certificate
+    let result = client.try_issue_certificate(
+        &String::from_str(&env, "cert-denylist-direct"),
+        &issuer,
+        &blocked,
+        &metadata_uri,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::RecipientDenied)));
+
+    // Cannot receive a transfer
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &blocked, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::RecipientDenied)));
+
+    // Succeeds again after removal
+    client.remove_from_denylist(&blocked);
+    assert!(!client.is_denylisted(&blocked));
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &blocked, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer(&transfer_id).to_address, blocked);
+}
+
+#[test]
+fn test_issuer_block_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-block");
+    let transfer_id = String::from_str(&env, "transfer-block");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmBlock"), &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    // Neither party can use the issuer lever
+    let result = client.try_issuer_block_transfer(&transfer_id, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    client.issuer_block_transfer(&transfer_id, &issuer);
+
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Cancelled);
+    assert_eq!(client.get_pending_transfer_count(&new_owner), 0);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("transfer_cancel"), cert_id.clone()).into_val(&env));
+
+    // The certificate is free for a new transfer
+    client.can_transfer(&cert_id, &owner, &new_owner);
+}
+
+#[test]
+fn test_initiate_transfer_v2_large_fee() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-large-fee");
+    let transfer_id = String::from_str(&env, "transfer-large-fee");
+
+    env.mock_all_auths();
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmLargeFee"), &None);
+
+    // Negative fees are rejected
+    let result = client.try_initiate_transfer_v2(
+        &String::from_str(&env, "transfer-negative-fee"),
+        &cert_id, &owner, &new_owner, &false, &-1i128, &None, &false, &None, &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // A fee beyond u64 range survives the round trip
+    let large_fee = u64::MAX as i128 * 1_000;
+    client.initiate_transfer_v2(&transfer_id, &cert_id, &owner, &new_owner, &false, &large_fee, &None, &false, &None, &None);
+    assert_eq!(client.get_transfer(&transfer_id).transfer_fee, large_fee);
+
+    client.accept_transfer(&transfer_id, &new_owner);
+    client.complete_transfer(&transfer_id, &new_owner);
+
+    let history = client.get_transfer_history(&cert_id);
+    assert_eq!(history.get(0).unwrap().transfer_fee, large_fee);
+}
+
+// Collect the ownership change events published by the last invocation
+fn ownership_changes(env: &Env, cert_id: &String) -> Vec<OwnershipChangedEvent> {
+    let expected_topics: Vec<soroban_sdk::Val> = (symbol_short!("own_chg"), cert_id.clone()).into_val(env);
+    let mut changes = Vec::new(env);
+    for (_, topics, data) in env.events().all().iter() {
+        if topics == expected_topics {
+            changes.push_back(data.into_val(env));
+        }
+    }
+    changes
+}
+
+#[test]
+fn test_ownership_changed_event_from_every_path() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let second_owner = Address::generate(&env);
+    let third_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-ownership-events");
+    let expiring_id = String::from_str(&env, "cert-ownership-expiring");
+    let metadata_uri = String::from_str(&env, "ipfs://QmOwnership");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate_with_expiry(&expiring_id, &issuer, &owner, &metadata_uri, &2_000u64, &false);
+
+    // Two-step transfer
+    let transfer_id = String::from_str(&env, "transfer-ownership-events");
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &second_owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_id, &second_owner);
+    client.complete_transfer(&transfer_id, &second_owner);
+    let changes = ownership_changes(&env, &cert_id);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.get(0).unwrap().old_owner, owner);
+    assert_eq!(changes.get(0).unwrap().new_owner, Some(second_owner.clone()));
+    assert_eq!(changes.get(0).unwrap().cause, OwnershipChangeCause::Transfer);
+
+    // Direct transfer
+    client.direct_transfer(&cert_id, &second_owner, &third_owner, &false);
+    let changes = ownership_changes(&env, &cert_id);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.get(0).unwrap().old_owner, second_owner);
+    assert_eq!(changes.get(0).unwrap().new_owner, Some(third_owner.clone()));
+    assert_eq!(changes.get(0).unwrap().cause, OwnershipChangeCause::DirectTransfer);
+
+    // Renounce
+    client.renounce_certificate(&cert_id, &third_owner);
+    let changes = ownership_changes(&env, &cert_id);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.get(0).unwrap().old_owner, third_owner);
+    assert_eq!(changes.get(0).unwrap().new_owner, None);
+    assert_eq!(changes.get(0).unwrap().cause, OwnershipChangeCause::Renounce);
+
+    // Reclaim after expiry
+    env.ledger().with_mut(|li| li.timestamp = 2_500);
+    client.reclaim_expired(&expiring_id);
+    let changes = ownership_changes(&env, &expiring_id);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes.get(0).unwrap().old_owner, owner);
+    assert_eq!(changes.get(0).unwrap().new_owner, Some(issuer.clone()));
+    assert_eq!(changes.get(0).unwrap().at, 2_500);
+    assert_eq!(changes.get(0).unwrap().cause, OwnershipChangeCause::Reclaim);
+}
+
+#[test]
+fn test_simulate_revoke_does_not_persist() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-simulate");
+    let reason = String::from_str(&env, "Preview");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmSimulate"), &None);
+    let before = client.get_certificate(&cert_id);
+
+    let preview = client.simulate_revoke(&cert_id, &reason);
+    assert!(preview.is_revoked());
+    assert_eq!(preview.revocation_reason, Some(reason.clone()));
+    assert_eq!(preview.revoked_by, Some(issuer.clone()));
+
+    // Storage is untouched
+    assert_eq!(client.get_certificate(&cert_id), before);
+    assert!(!client.is_revoked(&cert_id));
+    assert_eq!(client.get_revoked_count(), 0);
+
+    let result = client.try_simulate_revoke(&String::from_str(&env, "cert-missing"), &reason);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+
+    client.revoke_certificate(&cert_id, &reason);
+    let result = client.try_simulate_revoke(&cert_id, &reason);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+}
+
+#[test]
+fn test_require_memo_on_fee() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-memo-fee");
+    let transfer_id = String::from_str(&env, "transfer-memo-fee");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmMemoFee"), &None);
+
+    // Flag on: a fee without a memo is rejected
+    client.set_require_memo_on_fee(&true);
+    assert!(client.is_memo_required_on_fee());
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &100u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Flag off: the same transfer is accepted
+    client.set_require_memo_on_fee(&false);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &100u64, &None, &false, &None);
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Pending);
+}
+
+#[test]
+fn test_transfer_rate_limit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmRate");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_transfer_rate_limit(&2u32, &100u64);
+    assert_eq!(client.get_transfer_rate_limit(), (2u32, 100u64));
+
+    let cert_1 = String::from_str(&env, "cert-rate-1");
+    let cert_2 = String::from_str(&env, "cert-rate-2");
+    let cert_3 = String::from_str(&env, "cert-rate-3");
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_3, &issuer, &owner, &metadata_uri, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.initiate_transfer(&String::from_str(&env, "transfer-rate-1"), &cert_1, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.initiate_transfer(&String::from_str(&env, "transfer-rate-2"), &cert_2, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+
+    // The third transfer within the window is refused
+    let transfer_3 = String::from_str(&env, "transfer-rate-3");
+    env.ledger().with_mut(|li| li.timestamp = 1_099);
+    let result = client.try_initiate_transfer(&transfer_3, &cert_3, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::RateLimited)));
+
+    // Once the window has elapsed the allowance is restored
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    client.initiate_transfer(&transfer_3, &cert_3, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer_status(&transfer_3), TransferStatus::Pending);
+}
+
+#[test]
+fn test_get_certificate_opt() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-opt");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmOpt"), &None);
+
+    let cert = client.get_certificate_opt(&cert_id).unwrap();
+    assert_eq!(cert.owner, owner);
+    assert_eq!(client.get_certificate_opt(&String::from_str(&env, "cert-absent")), None);
+}
+
+#[test]
+fn test_get_config() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let token = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let config = client.get_config();
+    assert_eq!(config.admin, Some(admin.clone()));
+    assert_eq!(config.fee_config, None);
+    assert!(!config.allow_self_transfer);
+
+    client.set_fee_config(&token, &treasury, &500u32);
+    client.set_transfer_fee_limits(&10i128, &1_000i128);
+    client.set_require_memo_on_fee(&true);
+    client.set_allow_self_transfer(&true);
+    client.set_max_certs_per_owner(&25u32);
+    client.set_transfer_rate_limit(&5u32, &3_600u64);
+
+    let config = client.get_config();
+    assert_eq!(config.admin, Some(admin));
+    assert_eq!(config.pending_admin, None);
+    assert_eq!(
+        config.fee_config,
+        Some(FeeConfig { token, treasury, royalty_bps: 500 })
+    );
+    assert_eq!(config.min_transfer_fee, 10);
+    assert_eq!(config.max_transfer_fee, 1_000);
+    assert!(config.require_memo_on_fee);
+    assert!(config.allow_self_transfer);
+    assert_eq!(config.max_certs_per_owner, 25);
+    assert_eq!(config.max_transfers_per_window, 5);
+    assert_eq!(config.rate_window_seconds, 3_600);
+}
+
+#[test]
+fn test_accept_and_complete() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-accept-complete");
+    let transfer_id = String::from_str(&env, "transfer-accept-complete");
+    let memo = Some(String::from_str(&env, "Single call"));
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmAcceptComplete"), &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &memo, &false, &None);
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Pending);
+
+    client.accept_and_complete(&transfer_id, &new_owner);
+
+    let transfer = client.get_transfer(&transfer_id);
+    assert_eq!(transfer.status, TransferStatus::Completed);
+    assert!(transfer.accepted_at.is_some());
+    assert_eq!(client.get_certificate(&cert_id).owner, new_owner);
+    assert_eq!(client.get_pending_transfer_count(&new_owner), 0);
+
+    let history = client.get_transfer_history(&cert_id);
+    assert_eq!(history.len(), 1);
+    let entry = history.get(0).unwrap();
+    assert_eq!(entry.transfer_id, transfer_id);
+    assert_eq!(entry.to_address, new_owner);
+    assert_eq!(entry.memo, memo);
+}
+
+#[test]
+fn test_accept_and_complete_requires_allowed_completer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-accept-complete-policy");
+    let transfer_id = String::from_str(&env, "transfer-accept-complete-policy");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmAcceptComplete"), &None);
+    client.initiate_transfer(
+        &transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false,
+        &Some(CompleterPolicy::SenderOnly),
+    );
+
+    let result = client.try_accept_and_complete(&transfer_id, &new_owner);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Pending);
+}
+
+#[test]
+fn test_transfer_whitelist() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let accredited = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-whitelist");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmWhitelist"), &None);
+
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(accredited.clone());
+    client.set_transfer_whitelist(&cert_id, &recipients);
+    assert_eq!(client.get_transfer_whitelist(&cert_id), recipients);
+
+    let result = client.try_initiate_transfer(
+        &String::from_str(&env, "transfer-whitelist-outsider"),
+        &cert_id, &owner, &outsider, &false, &0u64, &None, &false, &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::RecipientNotWhitelisted)));
+
+    let transfer_id = String::from_str(&env, "transfer-whitelist-accredited");
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &accredited, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer(&transfer_id).to_address, accredited);
+}
+
+#[test]
+fn test_get_transfers_for_certificate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let rejecter = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-all-transfers");
+    let rejected_id = String::from_str(&env, "transfer-all-rejected");
+    let completed_id = String::from_str(&env, "transfer-all-completed");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmAllTransfers"), &None);
+
+    client.initiate_transfer(&rejected_id, &cert_id, &owner, &rejecter, &false, &0u64, &None, &false, &None);
+    client.reject_transfer(&rejected_id, &rejecter);
+
+    client.initiate_transfer(&completed_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&completed_id, &new_owner);
+    client.complete_transfer(&completed_id, &new_owner);
+
+    let transfers = client.get_transfers_for_certificate(&cert_id);
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers.get(0).unwrap().id, rejected_id);
+    assert_eq!(transfers.get(0).unwrap().status, TransferStatus::Rejected);
+    assert_eq!(transfers.get(1).unwrap().id, completed_id);
+    assert_eq!(transfers.get(1).unwrap().status, TransferStatus::Completed);
+
+    // Only the completed one shows in the history
+    assert_eq!(client.get_transfer_history(&cert_id).len(), 1);
+}
+
+#[test]
+fn test_issuer_auto_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let first_owner = Address::generate(&env);
+    let next_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-issuer-auto");
+    let issuer_transfer_id = String::from_str(&env, "transfer-issuer-auto");
+    let owner_transfer_id = String::from_str(&env, "transfer-owner-normal");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.issue_certificate(&cert_id, &issuer, &issuer, &String::from_str(&env, "ipfs://QmIssuerAuto"), &None);
+
+    // Disabled by default
+    let result = client.try_initiate_issuer_transfer(&issuer_transfer_id, &cert_id, &issuer, &first_owner, &None);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    // Issuer-originated transfer completes immediately
+    client.set_issuer_auto_transfer(&true);
+    assert!(client.get_config().issuer_auto_transfer);
+    client.initiate_issuer_transfer(&issuer_transfer_id, &cert_id, &issuer, &first_owner, &None);
+    assert_eq!(client.get_transfer_status(&issuer_transfer_id), TransferStatus::Completed);
+    assert_eq!(client.get_certificate(&cert_id).owner, first_owner);
+    assert_eq!(client.get_pending_transfer_count(&first_owner), 0);
+
+    // The new owner is not the issuer, so the path is closed to them
+    let result = client.try_initiate_issuer_transfer(
+        &String::from_str(&env, "transfer-owner-auto"),
+        &cert_id,
+        &first_owner,
+        &next_owner,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    // A normal owner transfer still requires acceptance
+    client.initiate_transfer(&owner_transfer_id, &cert_id, &first_owner, &next_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer_status(&owner_transfer_id), TransferStatus::Pending);
+    assert_eq!(client.get_certificate(&cert_id).owner, first_owner);
+}
+
+#[test]
+fn test_repair_pending_index() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmRepair");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let cert_1 = String::from_str(&env, "cert-repair-1");
+    let cert_2 = String::from_str(&env, "cert-repair-2");
+    let pending_id = String::from_str(&env, "transfer-repair-pending");
+    let completed_id = String::from_str(&env, "transfer-repair-completed");
+    let unknown_id = String::from_str(&env, "transfer-repair-unknown");
+
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+    client.initiate_transfer(&pending_id, &cert_1, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.initiate_transfer(&completed_id, &cert_2, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&completed_id, &recipient);
+    client.complete_transfer(&completed_id, &recipient);
+
+    // Seed stale entries as if an earlier bug had left them behind
+    env.as_contract(&contract_id, || {
+        let mut stale = Vec::new(&env);
+        stale.push_back(pending_id.clone());
+        stale.push_back(completed_id.clone());
+        stale.push_back(unknown_id.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::Transfer(TransferKey::PendingTransfers(recipient.clone())), &stale);
+    });
+    assert_eq!(client.get_pending_transfer_count(&recipient), 3);
+
+    // The stale entries were counted in the global total too
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Transfer(TransferKey::PendingTransferTotal), &3u32);
+    });
+
+    assert_eq!(client.repair_pending_index(&recipient), 2);
+
+    let pending = client.get_pending_transfers(&recipient);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap(), pending_id);
+
+    // The removed entries come off the total
+    assert_eq!(client.get_contract_stats().pending_transfers, 1);
+}
+
+#[test]
+fn test_revoke_by_hash() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-by-hash");
+    let content_hash = BytesN::from_array(&env, &[7u8; 32]);
+    let unknown_hash = BytesN::from_array(&env, &[8u8; 32]);
+    let metadata_uri = String::from_str(&env, "ipfs://QmByHash");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &Some(content_hash.clone()));
+
+    assert_eq!(client.get_id_by_hash(&content_hash), Some(cert_id.clone()));
+    assert_eq!(client.get_id_by_hash(&unknown_hash), None);
+
+    // A second certificate cannot claim the same hash
+    let result = client.try_issue_certificate(
+        &String::from_str(&env, "cert-by-hash-dup"),
+        &issuer,
+        &owner,
+        &metadata_uri,
+        &Some(content_hash.clone()),
+    );
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+
+    let revoked_id = client.revoke_by_hash(&content_hash, &String::from_str(&env, "Fraud"));
+    assert_eq!(revoked_id, cert_id);
+    assert!(client.is_revoked(&cert_id));
+
+    let result = client.try_revoke_by_hash(&content_hash, &String::from_str(&env, "Fraud"));
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+    let result = client.try_revoke_by_hash(&unknown_hash, &String::from_str(&env, "Fraud"));
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+}
+
+#[test]
+fn test_transfer_count_overflow() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-counter-overflow");
+    let transfer_id = String::from_str(&env, "transfer-counter-overflow");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmOverflow"), &None);
+
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Transfer(TransferKey::TransferCount), &u64::MAX);
+    });
+
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::CounterOverflow)));
+    assert_eq!(client.get_transfer_count(), u64::MAX);
+    assert!(!client.transfer_exists(&transfer_id));
+}
+
+#[test]
+fn test_contract_version() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(client.version(), (0u32, 1u32, 0u32));
+}
+
+// Count events from the last invocation published under the given topics
+fn count_events(env: &Env, name: soroban_sdk::Symbol, cert_id: &String) -> u32 {
+    let expected_topics: Vec<soroban_sdk::Val> = (name, cert_id.clone()).into_val(env);
+    let mut count = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        if topics == expected_topics {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_emit_events_toggle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let quiet_id = String::from_str(&env, "cert-events-off");
+    let loud_id = String::from_str(&env, "cert-events-on");
+    let metadata_uri = String::from_str(&env, "ipfs://QmEvents");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert!(client.are_events_enabled());
+    client.issue_certificate(&quiet_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&loud_id, &issuer, &owner, &metadata_uri, &None);
+
+    // Flag off: state changes without transfer events
+    client.set_emit_events(&false);
+    let transfer_id = String::from_str(&env, "transfer-events-off");
+    client.initiate_transfer(&transfer_id, &quiet_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(count_events(&env, symbol_short!("transfer_init"), &quiet_id), 0);
+    client.accept_transfer(&transfer_id, &new_owner);
+    assert_eq!(count_events(&env, symbol_short!("transfer_accept"), &quiet_id), 0);
+    client.complete_transfer(&transfer_id, &new_owner);
+    assert_eq!(count_events(&env, symbol_short!("transfer_complete"), &quiet_id), 0);
+    assert_eq!(client.get_certificate(&quiet_id).owner, new_owner);
+
+    // Flag on: events are published again
+    client.set_emit_events(&true);
+    let transfer_id = String::from_str(&env, "transfer-events-on");
+    client.initiate_transfer(&transfer_id, &loud_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(count_events(&env, symbol_short!("transfer_init"), &loud_id), 1);
+    client.accept_transfer(&transfer_id, &new_owner);
+    assert_eq!(count_events(&env, symbol_short!("transfer_accept"), &loud_id), 1);
+    client.complete_transfer(&transfer_id, &new_owner);
+    assert_eq!(count_events(&env, symbol_short!("transfer_complete"), &loud_id), 1);
+    assert_eq!(client.get_certificate(&loud_id).owner, new_owner);
+}
+
+#[test]
+fn test_reject_all_pending() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmRejectAll");
+
+    env.mock_all_auths();
+
+    let mut transfer_ids = Vec::new(&env);
+    for i in 0..3 {
+        let cert_id = String::from_str(&env, &format!("cert-reject-all-{}", i));
+        let transfer_id = String::from_str(&env, &format!("transfer-reject-all-{}", i));
+        client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+        client.initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+        transfer_ids.push_back(transfer_id);
+    }
+    assert_eq!(client.get_pending_transfer_count(&recipient), 3);
+
+    assert_eq!(client.reject_all_pending(&recipient), 3);
+
+    let mut reject_events = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        let name: soroban_sdk::Symbol = topics.get(0).unwrap().into_val(&env);
+        if name == symbol_short!("transfer_reject") {
+            reject_events += 1;
+        }
+    }
+    assert_eq!(reject_events, 3);
+
+    for transfer_id in transfer_ids.iter() {
+        assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Rejected);
+    }
+    assert_eq!(client.get_pending_transfer_count(&recipient), 0);
+    assert_eq!(client.get_contract_stats().pending_transfers, 0);
+    assert_eq!(client.try_reject_all_pending(&recipient), Ok(Ok(0)));
+}
+
+#[test]
+fn test_reissue_certificate_preserves_lineage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let old_id = String::from_str(&env, "cert-original");
+    let new_id = String::from_str(&env, "cert-reissued");
+    let new_uri = String::from_str(&env, "ipfs://QmReissued");
+
+    env.mock_all_auths();
+    client.issue_certificate(&old_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmOriginal"), &None);
+
+    client.reissue_certificate(&old_id, &new_id, &new_uri);
+
+    let old_cert = client.get_certificate(&old_id);
+    assert!(old_cert.revoked);
+    assert_eq!(old_cert.revocation_reason, Some(String::from_str(&env, "Superseded")));
+    assert_eq!(old_cert.superseded_by, Some(new_id.clone()));
+
+    let new_cert = client.get_certificate(&new_id);
+    assert!(!new_cert.revoked);
+    assert_eq!(new_cert.owner, owner);
+    assert_eq!(new_cert.issuer, issuer);
+    assert_eq!(new_cert.metadata_uri, new_uri);
+    assert_eq!(new_cert.supersedes, Some(old_id.clone()));
+    assert_eq!(new_cert.superseded_by, None);
+    // Recording the lineage is a tracked mutation
+    assert_eq!(new_cert.nonce, 1);
+
+    // A superseded certificate cannot be reissued again
+    let result = client.try_reissue_certificate(&old_id, &String::from_str(&env, "cert-reissued-2"), &new_uri);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+}
+
+#[test]
+fn test_get_active_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-active-transfer");
+    let transfer_id = String::from_str(&env, "transfer-active");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmActive"), &None);
+    assert_eq!(client.get_active_transfer(&cert_id), None);
+
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_active_transfer(&cert_id), Some(transfer_id.clone()));
+
+    client.accept_transfer(&transfer_id, &new_owner);
+    assert_eq!(client.get_active_transfer(&cert_id), Some(transfer_id.clone()));
+
+    client.complete_transfer(&transfer_id, &new_owner);
+    assert_eq!(client.get_active_transfer(&cert_id), None);
+}
+
+#[test]
+fn test_default_transfer_ttl() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmTtl");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_default_transfer_ttl(&100u64);
+
+    let default_cert = String::from_str(&env, "cert-ttl-default");
+    let custom_cert = String::from_str(&env, "cert-ttl-custom");
+    let default_transfer = String::from_str(&env, "transfer-ttl-default");
+    let custom_transfer = String::from_str(&env, "transfer-ttl-custom");
+    client.issue_certificate(&default_cert, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&custom_cert, &issuer, &owner, &metadata_uri, &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    // A zero TTL inherits the default, a non-zero one keeps its own
+    let default_ttl = Some(TransferOptions { complete_by: None, ttl_seconds: 0, data: None, prepay_fee: false });
+    let custom_ttl = Some(TransferOptions { complete_by: None, ttl_seconds: 500, data: None, prepay_fee: false });
+    client.initiate_transfer_v2(&default_transfer, &default_cert, &owner, &recipient, &false, &0i128, &None, &false, &None, &default_ttl);
+    client.initiate_transfer_v2(&custom_transfer, &custom_cert, &owner, &recipient, &false, &0i128, &None, &false, &None, &custom_ttl);
+    assert_eq!(client.get_transfer(&default_transfer).expires_at, Some(1_100));
+    assert_eq!(client.get_transfer(&custom_transfer).expires_at, Some(1_500));
+
+    // The deadline second itself still accepts
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    client.can_accept(&default_transfer, &recipient);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_101);
+    let result = client.try_accept_transfer(&default_transfer, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::TransferExpired)));
+    client.accept_transfer(&custom_transfer, &recipient);
+    assert_eq!(client.get_transfer_status(&custom_transfer), TransferStatus::Accepted);
+}
+
+#[test]
+fn test_has_valid_certificate_from() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let other_issuer = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let revoked_holder = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmGate");
+
+    env.mock_all_auths();
+
+    let valid_id = String::from_str(&env, "cert-gate-valid");
+    let other_id = String::from_str(&env, "cert-gate-other");
+    let revoked_id = String::from_str(&env, "cert-gate-revoked");
+    client.issue_certificate(&valid_id, &issuer, &holder, &metadata_uri, &None);
+    client.issue_certificate(&other_id, &other_issuer, &revoked_holder, &metadata_uri, &None);
+    client.issue_certificate(&revoked_id, &issuer, &revoked_holder, &metadata_uri, &None);
+    client.revoke_certificate(&revoked_id, &String::from_str(&env, "Revoked"));
+
+    assert!(client.has_valid_certificate_from(&holder, &issuer));
+    assert!(!client.has_valid_certificate_from(&holder, &other_issuer));
+
+    // The only certificate from the issuer is revoked
+    assert!(!client.has_valid_certificate_from(&revoked_holder, &issuer));
+    assert!(client.has_valid_certificate_from(&revoked_holder, &other_issuer));
+}
+
+#[test]
+fn test_revocation_grace_and_appeal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let appealed_id = String::from_str(&env, "cert-grace-appealed");
+    let lapsed_id = String::from_str(&env, "cert-grace-lapsed");
+    let metadata_uri = String::from_str(&env, "ipfs://QmGrace");
+    let reason = String::from_str(&env, "Disputed record");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.issue_certificate(&appealed_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&lapsed_id, &issuer, &owner, &metadata_uri, &None);
+
+    client.revoke_with_grace(&appealed_id, &reason, &600u64);
+    client.revoke_with_grace(&lapsed_id, &reason, &600u64);
+
+    // Appeal within the window keeps the certificate valid
+    env.ledger().with_mut(|li| li.timestamp = 1_300);
+    client.appeal_revocation(&appealed_id);
+    let appeal = client.get_revocation_appeal(&appealed_id).unwrap();
+    assert_eq!(appeal.appealed_by, owner);
+    assert_eq!(appeal.appealed_at, 1_300);
+    assert_eq!(appeal.reason, Some(reason.clone()));
+
+    // Without an appeal the revocation lands after the window
+    env.ledger().with_mut(|li| li.timestamp = 1_600);
+    assert!(!client.is_revoked(&appealed_id));
+    assert_eq!(client.verify_certificate(&appealed_id).status, CertificateStatus::Valid);
+    assert!(client.is_revoked(&lapsed_id));
+    assert_eq!(client.verify_certificate(&lapsed_id).status, CertificateStatus::Revoked);
+
+    let result = client.try_appeal_revocation(&lapsed_id);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+    assert_eq!(client.get_revocation_appeal(&lapsed_id), None);
+}
+
+#[test]
+fn test_issuer_uri_prefix() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-prefix");
+
+    env.mock_all_auths();
+    client.set_issuer_uri_prefix(&issuer, &String::from_str(&env, "ipfs://"));
+    assert_eq!(client.get_issuer_uri_prefix(&issuer), Some(String::from_str(&env, "ipfs://")));
+
+    // Non-matching URI is rejected at issuance
+    let result = client.try_issue_certificate(
+        &cert_id,
+        &issuer,
+        &owner,
+        &String::from_str(&env, "https://phish.example/ipfs://QmPrefix"),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Matching URI is accepted
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmPrefix"), &None);
+
+    // Updates are held to the same prefix
+    let nonce = client.get_certificate(&cert_id).nonce;
+    let result = client.try_update_metadata_uri_checked(
+        &cert_id,
+        &String::from_str(&env, "ipfx://QmPrefixV2"),
+        &nonce,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    client.update_metadata_uri_checked(&cert_id, &String::from_str(&env, "ipfs://QmPrefixV2"), &nonce, &None);
+    assert_eq!(client.get_certificate(&cert_id).metadata_uri, String::from_str(&env, "ipfs://QmPrefixV2"));
+}
+
+#[test]
+fn test_issue_and_initiate_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let registrar = Address::generate(&env);
+    let graduate = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-issue-transfer");
+    let transfer_id = String::from_str(&env, "transfer-issue-transfer");
+
+    env.mock_all_auths();
+    client.issue_and_initiate_transfer(
+        &cert_id,
+        &issuer,
+        &registrar,
+        &String::from_str(&env, "ipfs://QmIssueTransfer"),
+        &None,
+        &transfer_id,
+        &graduate,
+        &None,
+    );
+
+    // Both the issuer and the initial owner authorized the call
+    let auths = env.auths();
+    assert!(auths.iter().any(|(address, _)| *address == issuer));
+    assert!(auths.iter().any(|(address, _)| *address == registrar));
+
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.issuer, issuer);
+    assert_eq!(cert.owner, registrar);
+
+    let transfer = client.get_transfer(&transfer_id);
+    assert_eq!(transfer.status, TransferStatus::Pending);
+    assert_eq!(transfer.from_address, registrar);
+    assert_eq!(transfer.to_address, graduate);
+    assert_eq!(client.get_active_transfer(&cert_id), Some(transfer_id));
+}
+
+#[test]
+fn test_purged_transfer_id_cannot_be_reused() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-reuse-id");
+    let transfer_id = String::from_str(&env, "transfer-reuse-id");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmReuse"), &None);
+
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.reject_transfer(&transfer_id, &new_owner);
+    client.purge_transfer(&transfer_id, &owner);
+    assert_eq!(client.try_get_transfer(&transfer_id), Err(Ok(CertificateError::TransferNotFound)));
+    assert!(client.transfer_exists(&transfer_id));
+
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+}
+
+#[test]
+fn test_is_transferable_now() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmTransferable");
+    let reason = String::from_str(&env, "Check");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let missing = client.try_is_transferable_now(&String::from_str(&env, "cert-missing"));
+    assert_eq!(missing, Err(Ok(CertificateError::NotFound)));
+
+    // Revoked
+    let revoked_id = String::from_str(&env, "cert-transferable-revoked");
+    client.issue_certificate(&revoked_id, &issuer, &owner, &metadata_uri, &None);
+    assert!(client.is_transferable_now(&revoked_id));
+    client.revoke_certificate(&revoked_id, &reason);
+    assert!(!client.is_transferable_now(&revoked_id));
+
+    // Frozen, then unfrozen
+    let frozen_id = String::from_str(&env, "cert-transferable-frozen");
+    client.issue_certificate(&frozen_id, &issuer, &owner, &metadata_uri, &None);
+    client.freeze_certificate(&frozen_id, &issuer, &reason, &7);
+    assert!(!client.is_transferable_now(&frozen_id));
+    client.unfreeze_certificate(&frozen_id, &issuer, &reason);
+    assert!(client.is_transferable_now(&frozen_id));
+
+    // Suspended, then reinstated
+    let suspended_id = String::from_str(&env, "cert-transferable-suspended");
+    client.issue_certificate(&suspended_id, &issuer, &owner, &metadata_uri, &None);
+    client.suspend_certificate(&suspended_id, &reason);
+    assert!(!client.is_transferable_now(&suspended_id));
+    client.reinstate_from_suspension(&suspended_id);
+    assert!(client.is_transferable_now(&suspended_id));
+
+    // Active transfer, then cancelled
+    let active_id = String::from_str(&env, "cert-transferable-active");
+    let transfer_id = String::from_str(&env, "transfer-transferable");
+    client.issue_certificate(&active_id, &issuer, &owner, &metadata_uri, &None);
+    client.initiate_transfer(&transfer_id, &active_id, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    assert!(!client.is_transferable_now(&active_id));
+    client.cancel_transfer(&transfer_id, &owner);
+    assert!(client.is_transferable_now(&active_id));
+
+    // Expired
+    let expiring_id = String::from_str(&env, "cert-transferable-expiring");
+    client.issue_certificate_with_expiry(&expiring_id, &issuer, &owner, &metadata_uri, &2_000u64, &false);
+    assert!(client.is_transferable_now(&expiring_id));
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert!(!client.is_transferable_now(&expiring_id));
+}
+
+#[test]
+fn test_next_certificate_index_seeds_from_issuance_log() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmIndexSeed");
+
+    env.mock_all_auths();
+    client.issue_certificate(&String::from_str(&env, "cert-seed-1"), &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&String::from_str(&env, "cert-seed-2"), &issuer, &owner, &metadata_uri, &None);
+
+    // Simulate a deployment that predates the index key
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Index(IndexKey::NextCertificateIndex));
+    });
+    assert_eq!(client.get_next_certificate_index(), 2);
+
+    client.issue_certificate(&String::from_str(&env, "cert-seed-3"), &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_next_certificate_index(), 3);
+}
+
+#[test]
+fn test_next_certificate_index_is_monotonic() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmIndex");
+
+    env.mock_all_auths();
+    let mut last = client.get_next_certificate_index();
+    assert_eq!(last, 0);
+
+    let mut check = |expected_increase: u64| {
+        let next = client.get_next_certificate_index();
+        assert!(next >= last);
+        assert_eq!(next - last, expected_increase);
+        last = next;
+    };
+
+    let first_id = String::from_str(&env, "cert-index-1");
+    client.issue_certificate(&first_id, &issuer, &owner, &metadata_uri, &None);
+    check(1);
+
+    client.direct_transfer(&first_id, &owner, &new_owner, &false);
+    check(0);
+
+    client.revoke_certificate(&first_id, &String::from_str(&env, "Revoked"));
+    check(0);
+
+    let second_id = String::from_str(&env, "cert-index-2");
+    client.issue_certificate(&second_id, &issuer, &owner, &metadata_uri, &None);
+    check(1);
+
+    client.reissue_certificate(&second_id, &String::from_str(&env, "cert-index-3"), &metadata_uri);
+    check(1);
+
+    // A failed issuance does not move the index
+    let result = client.try_issue_certificate(&second_id, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+    check(0);
+}
+
+#[test]
+fn test_transfer_data_payload() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmData");
+    let cert_id = String::from_str(&env, "cert-data-1");
+    let transfer_id = String::from_str(&env, "transfer-data-1");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    let plain_id = String::from_str(&env, "transfer-data-plain");
+    client.initiate_transfer(&plain_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer(&plain_id).data, None);
+    client.cancel_transfer(&plain_id, &owner);
+
+    // The payload is fixed at initiation and size-bounded
+    let payload = Bytes::from_slice(&env, b"invoice:INV-2024-0042");
+    let oversized = Some(TransferOptions { complete_by: None, ttl_seconds: 0, data: Some(Bytes::from_slice(&env, &[7u8; 513])), prepay_fee: false });
+    let result = client.try_initiate_transfer_v2(&transfer_id, &cert_id, &owner, &recipient, &false, &0i128, &None, &false, &None, &oversized);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    let options = Some(TransferOptions { complete_by: None, ttl_seconds: 0, data: Some(payload.clone()), prepay_fee: false });
+    client.initiate_transfer_v2(&transfer_id, &cert_id, &owner, &recipient, &false, &0i128, &None, &false, &None, &options);
+    assert_eq!(client.get_transfer(&transfer_id).data, Some(payload.clone()));
+
+    client.accept_transfer(&transfer_id, &recipient);
+    client.complete_transfer(&transfer_id, &recipient);
+
+    let history = client.get_transfer_history(&cert_id);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().data, Some(payload));
+}
+
+#[test]
+fn test_find_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let rejecter = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-find-transfer");
+    let rejected_id = String::from_str(&env, "transfer-find-rejected");
+    let pending_id = String::from_str(&env, "transfer-find-pending");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmFind"), &None);
+    assert!(client.find_transfer(&cert_id, &TransferStatus::Pending).is_none());
+
+    client.initiate_transfer(&rejected_id, &cert_id, &owner, &rejecter, &false, &0u64, &None, &false, &None);
+    client.reject_transfer(&rejected_id, &rejecter);
+    client.initiate_transfer(&pending_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+
+    let pending = client.find_transfer(&cert_id, &TransferStatus::Pending).unwrap();
+    assert_eq!(pending.id, pending_id);
+    assert_eq!(pending.to_address, recipient);
+    assert_eq!(client.find_transfer(&cert_id, &TransferStatus::Rejected).unwrap().id, rejected_id);
+    assert!(client.find_transfer(&cert_id, &TransferStatus::Completed).is_none());
+}
+
+#[contract]
+pub struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn setup(env: Env, target: Address, transfer_id: String, executor: Address) {
+        env.storage().instance().set(&symbol_short!("target"), &target);
+        env.storage().instance().set(&symbol_short!("transfer"), &transfer_id);
+        env.storage().instance().set(&symbol_short!("executor"), &executor);
+    }
+
+    // Tries to complete the same transfer again while the fee is being paid
+    pub fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+        let target: Address = env.storage().instance().get(&symbol_short!("target")).unwrap();
+        let transfer_id: String = env.storage().instance().get(&symbol_short!("transfer")).unwrap();
+        let executor: Address = env.storage().instance().get(&symbol_short!("executor")).unwrap();
+
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &target,
+            &Symbol::new(&env, "complete_transfer"),
+            vec![&env, transfer_id.into_val(&env), executor.into_val(&env)],
+        );
+        env.storage().instance().set(&symbol_short!("reentered"), &matches!(result, Ok(Ok(()))));
+    }
+
+    pub fn reentered(env: Env) -> bool {
+        env.storage().instance().get(&symbol_short!("reentered")).unwrap_or(false)
+    }
+}
+
+#[test]
+fn test_reentrancy_guard_blocks_complete_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+    let token_id = env.register_contract(None, ReentrantToken);
+    let token = ReentrantTokenClient::new(&env, &token_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-reentrancy");
+    let transfer_id = String::from_str(&env, "transfer-reentrancy");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_fee_config(&token_id, &treasury, &500);
+    token.setup(&contract_id, &transfer_id, &new_owner);
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmReenter"), &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &new_owner, &false, &1_000u64, &None, &false, &None);
+    client.accept_transfer(&transfer_id, &new_owner);
+    client.complete_transfer(&transfer_id, &new_owner);
+
+    // The nested completion was refused and the transfer completed once
+    assert!(!token.reentered());
+    assert_eq!(client.get_certificate(&cert_id).owner, new_owner);
+    assert_eq!(client.get_transfer_history(&cert_id).len(), 1);
+    assert_eq!(ownership_changes(&env, &cert_id).len(), 1);
+
+    // A held lock rejects token-moving calls outright
+    let second_cert = String::from_str(&env, "cert-reentrancy-2");
+    let second_transfer = String::from_str(&env, "transfer-reentrancy-2");
+    client.issue_certificate(&second_cert, &issuer, &owner, &String::from_str(&env, "ipfs://QmReenter"), &None);
+    client.initiate_transfer(&second_transfer, &second_cert, &owner, &new_owner, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&second_transfer, &new_owner);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&DataKey::Lock, &true);
+    });
+    let result = client.try_complete_transfer(&second_transfer, &new_owner);
+    assert_eq!(result, Err(Ok(CertificateError::Reentrancy)));
+}
+
+#[test]
+fn test_owner_alias() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    assert_eq!(client.get_owner_alias(&owner), None);
+
+    client.set_owner_alias(&owner, &String::from_str(&env, "Alice"));
+    assert_eq!(client.get_owner_alias(&owner), Some(String::from_str(&env, "Alice")));
+
+    client.set_owner_alias(&owner, &String::from_str(&env, "Alice Cooper"));
+    assert_eq!(client.get_owner_alias(&owner), Some(String::from_str(&env, "Alice Cooper")));
+    assert_eq!(client.get_owner_alias(&other), None);
+
+    // Aliases are capped at 64 characters
+    let too_long = String::from_str(&env, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    let result = client.try_set_owner_alias(&owner, &too_long);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    assert_eq!(client.get_owner_alias(&owner), Some(String::from_str(&env, "Alice Cooper")));
+}
+
+#[test]
+fn test_revocation_dispute_upheld() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-dispute-upheld");
+    let statement = String::from_str(&env, "Revoked in error");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmDispute"), &None);
+
+    // Only revoked certificates can be disputed
+    let result = client.try_dispute_revocation(&cert_id, &owner, &statement);
+    assert_eq!(result, Err(Ok(CertificateError::NotRevoked)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Fraud"));
+
+    let result = client.try_dispute_revocation(&cert_id, &stranger, &statement);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    client.dispute_revocation(&cert_id, &owner, &statement);
+    let result = client.try_dispute_revocation(&cert_id, &owner, &statement);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+
+    let result = client.try_resolve_dispute(&cert_id, &stranger, &true);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.resolve_dispute(&cert_id, &issuer, &true);
+
+    let dispute = client.get_dispute(&cert_id).unwrap();
+    assert_eq!(dispute.disputed_by, owner);
+    assert_eq!(dispute.statement, statement);
+    assert_eq!(dispute.disputed_at, 1_000);
+    assert_eq!(dispute.resolved_by, Some(issuer));
+    assert_eq!(dispute.resolved_at, Some(2_000));
+    assert_eq!(dispute.upheld, Some(true));
+    assert!(client.get_certificate(&cert_id).revoked);
+
+    // A resolved dispute cannot be resolved again
+    let result = client.try_resolve_dispute(&cert_id, &issuer, &false);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+}
+
+#[test]
+fn test_revocation_dispute_reinstates() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-dispute-reinstate");
+    let late_id = String::from_str(&env, "cert-dispute-late");
+    let metadata_uri = String::from_str(&env, "ipfs://QmDispute");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&late_id, &issuer, &owner, &metadata_uri, &None);
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Fraud"));
+    client.revoke_certificate(&late_id, &String::from_str(&env, "Fraud"));
+    assert_eq!(client.get_contract_stats().total_revoked, 2);
+
+    client.dispute_revocation(&cert_id, &owner, &String::from_str(&env, "Evidence attached"));
+    client.resolve_dispute(&cert_id, &admin, &false);
+
+    let cert = client.get_certificate(&cert_id);
+    assert!(!cert.revoked);
+    assert_eq!(cert.revocation_reason, None);
+    assert_eq!(cert.revoked_at, None);
+    assert_eq!(client.get_dispute(&cert_id).unwrap().upheld, Some(false));
+    assert_eq!(client.get_contract_stats().total_revoked, 1);
+
+    // Disputes are only accepted during the challenge period
+    env.ledger().with_mut(|li| li.timestamp = 30 * 24 * 60 * 60 + 1);
+    let result = client.try_dispute_revocation(&late_id, &owner, &String::from_str(&env, "Too late"));
+    assert_eq!(result, Err(Ok(CertificateError::DisputeWindowClosed)));
+}
+
+#[test]
+fn test_has_key_diagnostic() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-has-key");
+    let transfer_id = String::from_str(&env, "transfer-has-key");
+
+    env.mock_all_auths();
+
+    // Nothing to authenticate against before initialization
+    let result = client.try_has_key(&0u32, &cert_id);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    client.initialize(&admin);
+    assert!(!client.has_key(&0u32, &cert_id));
+    assert!(!client.has_key(&1u32, &transfer_id));
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmHasKey"), &None);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+
+    assert!(client.has_key(&0u32, &cert_id));
+    assert!(client.has_key(&1u32, &transfer_id));
+    assert!(client.has_key(&2u32, &cert_id));
+    assert!(!client.has_key(&3u32, &cert_id));
+    assert!(!client.has_key(&1u32, &cert_id));
+
+    let result = client.try_has_key(&99u32, &cert_id);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+}
+
+#[test]
+fn test_require_owner_consent_on_issue() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmConsent");
+    let first_id = String::from_str(&env, "cert-consent-1");
+    let second_id = String::from_str(&env, "cert-consent-2");
+    let third_id = String::from_str(&env, "cert-consent-3");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert!(!client.is_issue_consent_required());
+
+    // Off by default: the issuer's auth alone is enough
+    let first_args = (first_id.clone(), issuer.clone(), owner.clone(), metadata_uri.clone(), None::<BytesN<32>>).into_val(&env);
+    client
+        .mock_auths(&[MockAuth {
+            address: &issuer,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "issue_certificate",
+                args: first_args,
+                sub_invokes: &[],
+            },
+        }])
+        .issue_certificate(&first_id, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_certificate(&first_id).owner, owner);
+
+    env.mock_all_auths();
+    client.set_issue_consent_required(&true);
+    assert!(client.is_issue_consent_required());
+    assert!(client.get_config().require_owner_consent_on_issue);
+
+    // With the flag on, issuance without the owner's auth fails
+    let second_args = (second_id.clone(), issuer.clone(), owner.clone(), metadata_uri.clone(), None::<BytesN<32>>).into_val(&env);
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &issuer,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "issue_certificate",
+                args: second_args,
+                sub_invokes: &[],
+            },
+        }])
+        .try_issue_certificate(&second_id, &issuer, &owner, &metadata_uri, &None);
+    assert!(result.is_err());
+    assert!(client.get_certificate_opt(&second_id).is_none());
+
+    env.mock_all_auths();
+    client.issue_certificate(&third_id, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_certificate(&third_id).owner, owner);
+
+    // Reissuance goes through the same check
+    let fourth_id = String::from_str(&env, "cert-consent-4");
+    let reissue_args = (third_id.clone(), fourth_id.clone(), metadata_uri.clone()).into_val(&env);
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &issuer,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "reissue_certificate",
+                args: reissue_args,
+                sub_invokes: &[],
+            },
+        }])
+        .try_reissue_certificate(&third_id, &fourth_id, &metadata_uri);
+    assert!(result.is_err());
+    assert!(client.get_certificate_opt(&fourth_id).is_none());
+}
+
+#[test]
+fn test_total_fees_for_certificate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let first_owner = Address::generate(&env);
+    let second_owner = Address::generate(&env);
+    let third_owner = Address::generate(&env);
+    let rejecter = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-fee-total");
+    let first_transfer = String::from_str(&env, "transfer-fee-total-1");
+    let second_transfer = String::from_str(&env, "transfer-fee-total-2");
+    let rejected_transfer = String::from_str(&env, "transfer-fee-total-rejected");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &first_owner, &String::from_str(&env, "ipfs://QmFees"), &None);
+    assert_eq!(client.get_total_fees_for_certificate(&cert_id), 0);
+
+    client.initiate_transfer(&first_transfer, &cert_id, &first_owner, &second_owner, &false, &250u64, &None, &false, &None);
+    client.accept_transfer(&first_transfer, &second_owner);
+    client.complete_transfer(&first_transfer, &second_owner);
+
+    // Rejected transfers never reach the history, so their fee is not counted
+    client.initiate_transfer(&rejected_transfer, &cert_id, &second_owner, &rejecter, &false, &999u64, &None, &false, &None);
+    client.reject_transfer(&rejected_transfer, &rejecter);
+
+    client.initiate_transfer(&second_transfer, &cert_id, &second_owner, &third_owner, &false, &400u64, &None, &false, &None);
+    client.accept_transfer(&second_transfer, &third_owner);
+    client.complete_transfer(&second_transfer, &third_owner);
+
+    assert_eq!(client.get_total_fees_for_certificate(&cert_id), 650);
+}
+
+#[test]
+fn test_rebuild_owner_index() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmRebuild");
+    let cert_1 = String::from_str(&env, "cert-rebuild-1");
+    let cert_2 = String::from_str(&env, "cert-rebuild-2");
+    let missing = String::from_str(&env, "cert-rebuild-missing");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+
+    // Drop the index as if the certificates predated it
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Index(IndexKey::OwnerCertificates(owner.clone())));
+    });
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 0);
+
+    let ids = vec![&env, cert_1.clone(), cert_2.clone(), missing];
+    assert_eq!(client.rebuild_owner_index(&ids), 2);
+
+    let owned = client.get_certificates_by_owner(&owner);
+    assert_eq!(owned, vec![&env, cert_1, cert_2]);
+
+    // Rebuilding again adds nothing
+    assert_eq!(client.rebuild_owner_index(&ids), 0);
+    assert_eq!(client.get_certificates_by_owner(&owner).len(), 2);
+}
+
+#[test]
+fn test_weighted_revocation_threshold() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let lead = Address::generate(&env);
+    let partner_a = Address::generate(&env);
+    let partner_b = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmWeighted");
+    let reason = String::from_str(&env, "Co-issuers agreed");
+    let lead_cert = String::from_str(&env, "cert-weighted-lead");
+    let partners_cert = String::from_str(&env, "cert-weighted-partners");
+
+    env.mock_all_auths();
+    client.issue_certificate(&lead_cert, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&partners_cert, &issuer, &owner, &metadata_uri, &None);
+
+    let weights = vec![&env, (lead.clone(), 2u32), (partner_a.clone(), 1u32), (partner_b.clone(), 1u32)];
+
+    // The threshold must be reachable with the given weights
+    let result = client.try_set_revocation_weights(&lead_cert, &weights, &5u32);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    client.set_revocation_weights(&lead_cert, &weights, &3u32);
+    client.set_revocation_weights(&partners_cert, &weights, &3u32);
+
+    // Lead (2) plus one partner (1) meets the threshold
+    assert!(!client.approve_weighted_revocation(&lead_cert, &lead, &reason));
+    assert_eq!(client.get_revocation_approval_weight(&lead_cert), 2);
+    assert!(!client.get_certificate(&lead_cert).revoked);
+    assert!(client.approve_weighted_revocation(&lead_cert, &partner_a, &reason));
+    let cert = client.get_certificate(&lead_cert);
+    assert!(cert.revoked);
+    assert_eq!(cert.revoked_by, Some(partner_a.clone()));
+
+    // Both partners together only reach 2
+    let result = client.try_approve_weighted_revocation(&partners_cert, &outsider, &reason);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    assert!(!client.approve_weighted_revocation(&partners_cert, &partner_a, &reason));
+    let result = client.try_approve_weighted_revocation(&partners_cert, &partner_a, &reason);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+    assert!(!client.approve_weighted_revocation(&partners_cert, &partner_b, &reason));
+    assert_eq!(client.get_revocation_approval_weight(&partners_cert), 2);
+    assert!(!client.get_certificate(&partners_cert).revoked);
+}
+
+#[test]
+fn test_status_counts() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmCounts");
+    let cert_1 = String::from_str(&env, "cert-counts-1");
+    let cert_2 = String::from_str(&env, "cert-counts-2");
+    let completed_id = String::from_str(&env, "transfer-counts-completed");
+    let rejected_id = String::from_str(&env, "transfer-counts-rejected");
+    let cancelled_id = String::from_str(&env, "transfer-counts-cancelled");
+
+    let counts = |pending, accepted, completed, rejected, cancelled| StatusCounts {
+        pending,
+        accepted,
+        completed,
+        rejected,
+        cancelled,
+    };
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_status_counts(), counts(0, 0, 0, 0, 0));
+
+    client.initiate_transfer(&completed_id, &cert_1, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.initiate_transfer(&rejected_id, &cert_2, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_status_counts(), counts(2, 0, 0, 0, 0));
+
+    client.accept_transfer(&completed_id, &recipient);
+    assert_eq!(client.get_status_counts(), counts(1, 1, 0, 0, 0));
+
+    client.complete_transfer(&completed_id, &recipient);
+    assert_eq!(client.get_status_counts(), counts(1, 0, 1, 0, 0));
+
+    client.reject_transfer(&rejected_id, &recipient);
+    assert_eq!(client.get_status_counts(), counts(0, 0, 1, 1, 0));
+
+    client.initiate_transfer(&cancelled_id, &cert_2, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_status_counts(), counts(1, 0, 1, 1, 0));
+
+    client.cancel_transfer(&cancelled_id, &owner);
+    assert_eq!(client.get_status_counts(), counts(0, 0, 1, 1, 1));
+
+    // Purging a finished request leaves its status counted
+    client.purge_transfer(&cancelled_id, &owner);
+    assert_eq!(client.get_status_counts(), counts(0, 0, 1, 1, 1));
+}
+
+#[test]
+fn test_backup_uris() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-backups");
+    let plain_id = String::from_str(&env, "cert-backups-plain");
+    let primary = String::from_str(&env, "ipfs://QmPrimary");
+    let mirror_1 = String::from_str(&env, "https://mirror-1.example/cert");
+    let mirror_2 = String::from_str(&env, "https://mirror-2.example/cert");
+
+    env.mock_all_auths();
+    client.issue_certificate(&plain_id, &issuer, &owner, &primary, &None);
+    assert_eq!(client.get_certificate(&plain_id).backup_uris.len(), 0);
+
+    client.issue_certificate_with_backups(&cert_id, &issuer, &owner, &primary, &None, &vec![&env, mirror_1.clone()]);
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.backup_uris, vec![&env, mirror_1.clone()]);
+    assert_eq!(cert.nonce, 0);
+
+    client.add_backup_uri(&cert_id, &mirror_2);
+    assert_eq!(client.get_certificate(&cert_id).backup_uris, vec![&env, mirror_1.clone(), mirror_2.clone()]);
+    let result = client.try_add_backup_uri(&cert_id, &mirror_2);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+
+    client.remove_backup_uri(&cert_id, &mirror_1);
+    assert_eq!(client.get_certificate(&cert_id).backup_uris, vec![&env, mirror_2.clone()]);
+    let result = client.try_remove_backup_uri(&cert_id, &mirror_1);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+
+    // At most five backups per certificate
+    for i in 0..4u32 {
+        let uri = String::from_str(&env, &format!("https://mirror-extra-{}.example/cert", i));
+        client.add_backup_uri(&cert_id, &uri);
+    }
+    assert_eq!(client.get_certificate(&cert_id).backup_uris.len(), 5);
+    let result = client.try_add_backup_uri(&cert_id, &String::from_str(&env, "https://one-too-many.example"));
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+}
+
+#[test]
+fn test_cleanup_expired_transfer_emits_expired_event() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-expire-event");
+    let transfer_id = String::from_str(&env, "transfer-expire-event");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_default_transfer_ttl(&100u64);
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmExpire"), &None);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+
+    // Not cleanable before the deadline
+    let result = client.try_cleanup_expired_transfer(&transfer_id);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidTransferStatus)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_101);
+    client.cleanup_expired_transfer(&transfer_id);
+
+    assert_eq!(count_events(&env, symbol_short!("transfer_expire"), &cert_id), 1);
+    assert_eq!(count_events(&env, symbol_short!("transfer_cancel"), &cert_id), 0);
+    let (_, _, data) = env.events().all().last().unwrap();
+    let event_data: TransferExpiredEvent = data.into_val(&env);
+    assert_eq!(event_data.transfer_id, transfer_id);
+    assert_eq!(event_data.certificate_id, cert_id);
+    assert_eq!(event_data.expired_at, 1_101);
+
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Cancelled);
+    assert_eq!(client.get_active_transfer(&cert_id), None);
+    assert_eq!(client.get_pending_transfer_count(&recipient), 0);
+}
+
+#[test]
+fn test_prepaid_transfer_fee_escrow() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmPrepaid");
+    let cert_id = String::from_str(&env, "cert-prepaid");
+    let rejected_id = String::from_str(&env, "transfer-prepaid-rejected");
+    let cancelled_id = String::from_str(&env, "transfer-prepaid-cancelled");
+    let completed_id = String::from_str(&env, "transfer-prepaid-completed");
+
+    env.mock_all_auths();
+
+    let fee_token = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &fee_token.address());
+    let token = soroban_sdk::token::Client::new(&env, &fee_token.address());
+    token_admin.mint(&owner, &10_000);
+    token_admin.mint(&recipient, &10_000);
+
+    client.initialize(&admin);
+    client.set_fee_config(&fee_token.address(), &treasury, &500);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+
+    // Prepaying needs a fee to escrow
+    let prepay = Some(TransferOptions { complete_by: None, ttl_seconds: 0, data: None, prepay_fee: true });
+    let result = client.try_initiate_transfer_v2(&rejected_id, &cert_id, &owner, &recipient, &false, &0i128, &None, &false, &None, &prepay);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Rejection refunds the escrowed fee
+    client.initiate_transfer_v2(&rejected_id, &cert_id, &owner, &recipient, &false, &1_000i128, &None, &false, &None, &prepay);
+    assert_eq!(token.balance(&owner), 9_000);
+    assert_eq!(token.balance(&contract_id), 1_000);
+    client.reject_transfer(&rejected_id, &recipient);
+    assert_eq!(token.balance(&owner), 10_000);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    // So does cancellation
+    client.initiate_transfer_v2(&cancelled_id, &cert_id, &owner, &recipient, &false, &1_000i128, &None, &false, &None, &prepay);
+    client.cancel_transfer(&cancelled_id, &owner);
+    assert_eq!(token.balance(&owner), 10_000);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    // Completion releases the escrow; the recipient pays nothing
+    client.initiate_transfer_v2(&completed_id, &cert_id, &owner, &recipient, &false, &1_000i128, &None, &false, &None, &prepay);
+    client.accept_transfer(&completed_id, &recipient);
+    client.complete_transfer(&completed_id, &recipient);
+    assert_eq!(token.balance(&owner), 9_000);
+    assert_eq!(token.balance(&recipient), 10_000);
+    assert_eq!(token.balance(&issuer), 50);
+    assert_eq!(token.balance(&treasury), 950);
+    assert_eq!(token.balance(&contract_id), 0);
+}
+
+#[contract]
+pub struct MockRevocationHook;
+
+#[contractimpl]
+impl MockRevocationHook {
+    pub fn on_certificate_revoked(env: Env, certificate_id: String) {
+        env.storage().instance().set(&symbol_short!("revoked"), &certificate_id);
+    }
+
+    pub fn last_revoked(env: Env) -> Option<String> {
+        env.storage().instance().get(&symbol_short!("revoked"))
+    }
+}
+
+#[contract]
+pub struct FailingRevocationHook;
+
+#[contractimpl]
+impl FailingRevocationHook {
+    pub fn on_certificate_revoked(_env: Env, _certificate_id: String) {
+        panic!("hook unavailable");
+    }
+}
+
+#[test]
+fn test_revocation_hook_records_call() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+    let hook_id = env.register_contract(None, MockRevocationHook);
+    let hook = MockRevocationHookClient::new(&env, &hook_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-hook");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_revocation_hook(&Some(hook_id.clone()), &false);
+    assert_eq!(client.get_revocation_hook(), Some(hook_id));
+    assert!(!client.is_revocation_hook_strict());
+
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmHook"), &None);
+    assert_eq!(hook.last_revoked(), None);
+
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Revoked"));
+    assert_eq!(hook.last_revoked(), Some(cert_id));
+}
+
+#[test]
+fn test_failing_revocation_hook_does_not_block_revocation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+    let hook_id = env.register_contract(None, FailingRevocationHook);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-hook-failing");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmHook"), &None);
+    client.set_revocation_hook(&Some(hook_id), &false);
+
+    // The revocation goes through and the failure is reported
+    client.revoke_certificate(&cert_id, &String::from_str(&env, "Revoked"));
+    assert_eq!(count_events(&env, symbol_short!("hook_fail"), &cert_id), 1);
+    assert!(client.get_certificate(&cert_id).revoked);
+
+    // The lock was released despite the failure
+    let next_id = String::from_str(&env, "cert-hook-failing-2");
+    client.issue_certificate(&next_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmHook2"), &None);
+    client.revoke_certificate(&next_id, &String::from_str(&env, "Revoked"));
+    assert!(client.get_certificate(&next_id).revoked);
+}
+
+#[test]
+fn test_strict_revocation_hook_reverts_revocation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+    let hook_id = env.register_contract(None, FailingRevocationHook);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-hook-strict");
+    let reason = String::from_str(&env, "Revoked");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmHook"), &None);
+    client.set_revocation_hook(&Some(hook_id), &true);
+    assert!(client.is_revocation_hook_strict());
+    assert!(client.get_config().strict_revocation_hook);
+
+    let result = client.try_revoke_certificate(&cert_id, &reason);
+    assert_eq!(result, Err(Ok(CertificateError::RevocationHookFailed)));
+    assert!(!client.get_certificate(&cert_id).revoked);
+    assert_eq!(client.get_revoked_count(), 0);
+
+    // Dropping strict mode lets the revocation through
+    client.set_revocation_hook(&client.get_revocation_hook(), &false);
+    client.revoke_certificate(&cert_id, &reason);
+    assert!(client.get_certificate(&cert_id).revoked);
+}
+
+#[test]
+fn test_issue_certificate_idempotent() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-idempotent");
+    let metadata_uri = String::from_str(&env, "ipfs://QmIdempotent");
+
+    env.mock_all_auths();
+
+    // First call issues
+    assert!(client.issue_certificate_idempotent(&cert_id, &issuer, &owner, &metadata_uri, &None));
+    let issued = client.get_certificate(&cert_id);
+    assert_eq!(issued.owner, owner);
+
+    // A matching retry is a no-op
+    assert!(!client.issue_certificate_idempotent(&cert_id, &issuer, &owner, &metadata_uri, &None));
+    assert_eq!(client.get_certificate(&cert_id).nonce, issued.nonce);
+    assert_eq!(client.get_next_certificate_index(), 1);
+
+    // A different certificate under the same ID is a conflict
+    let result = client.try_issue_certificate_idempotent(&cert_id, &issuer, &other_owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    let result = client.try_issue_certificate_idempotent(
+        &cert_id,
+        &issuer,
+        &owner,
+        &String::from_str(&env, "ipfs://QmSomethingElse"),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+}
+
+#[test]
+fn test_get_transfer_statuses() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmStatuses");
+    let cert_1 = String::from_str(&env, "cert-statuses-1");
+    let cert_2 = String::from_str(&env, "cert-statuses-2");
+    let pending_id = String::from_str(&env, "transfer-statuses-pending");
+    let rejected_id = String::from_str(&env, "transfer-statuses-rejected");
+    let unknown_id = String::from_str(&env, "transfer-statuses-unknown");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+    client.initiate_transfer(&pending_id, &cert_1, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.initiate_transfer(&rejected_id, &cert_2, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.reject_transfer(&rejected_id, &recipient);
+
+    let ids = vec![&env, unknown_id.clone(), pending_id, unknown_id, rejected_id];
+    let statuses = client.get_transfer_statuses(&ids);
+    assert_eq!(
+        statuses,
+        vec![
+            &env,
+            None,
+            Some(TransferStatus::Pending),
+            None,
+            Some(TransferStatus::Rejected),
+        ]
+    );
+
+    // Requests are capped at the batch size
+    let mut too_many = Vec::new(&env);
+    for i in 0..51u32 {
+        too_many.push_back(String::from_str(&env, &format!("transfer-{}", i)));
+    }
+    let result = client.try_get_transfer_statuses(&too_many);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+}
+
+#[test]
+fn test_certificate_tags() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmTags");
+    let cert_1 = String::from_str(&env, "cert-tags-1");
+    let cert_2 = String::from_str(&env, "cert-tags-2");
+    let rust = String::from_str(&env, "rust");
+    let advanced = String::from_str(&env, "advanced");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&cert_2, &issuer, &owner, &metadata_uri, &None);
+
+    client.add_tag(&cert_1, &rust);
+    client.add_tag(&cert_1, &advanced);
+    client.add_tag(&cert_2, &rust);
+    let result = client.try_add_tag(&cert_1, &rust);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+
+    assert_eq!(client.get_tags(&cert_1), vec![&env, rust.clone(), advanced.clone()]);
+    assert_eq!(client.get_certificates_by_tag(&rust), vec![&env, cert_1.clone(), cert_2.clone()]);
+    assert_eq!(client.get_certificates_by_tag(&advanced), vec![&env, cert_1.clone()]);
+
+    client.remove_tag(&cert_1, &rust);
+    assert_eq!(client.get_tags(&cert_1), vec![&env, advanced.clone()]);
+    assert_eq!(client.get_certificates_by_tag(&rust), vec![&env, cert_2.clone()]);
+    assert_eq!(client.get_certificates_by_tag(&advanced), vec![&env, cert_1.clone()]);
+
+    let result = client.try_remove_tag(&cert_1, &rust);
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+}
+
+#[test]
+fn test_min_age_before_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-min-age");
+    let transfer_id = String::from_str(&env, "transfer-min-age");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.set_min_age_before_transfer(&1_000u64);
+    assert_eq!(client.get_min_age_before_transfer(), 1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmMinAge"), &None);
+    assert!(!client.is_transferable_now(&cert_id));
+
+    // Straight after issuance the transfer is refused
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateTooNew)));
+
+    env.ledger().with_mut(|li| li.timestamp = 5_999);
+    let result = client.try_initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateTooNew)));
+
+    // Once the holding period has passed it goes through
+    env.ledger().with_mut(|li| li.timestamp = 6_000);
+    assert!(client.is_transferable_now(&cert_id));
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Pending);
+}
+
+#[test]
+fn test_certificates_expiring_before() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmExpiring");
+    let late = String::from_str(&env, "cert-expiring-late");
+    let soon = String::from_str(&env, "cert-expiring-soon");
+    let revoked = String::from_str(&env, "cert-expiring-revoked");
+    let middle = String::from_str(&env, "cert-expiring-middle");
+    let never = String::from_str(&env, "cert-expiring-never");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    // Issued out of expiry order
+    client.issue_certificate_with_expiry(&late, &issuer, &owner, &metadata_uri, &9_000u64, &false);
+    client.issue_certificate_with_expiry(&soon, &issuer, &owner, &metadata_uri, &2_000u64, &false);
+    client.issue_certificate_with_expiry(&revoked, &issuer, &owner, &metadata_uri, &2_500u64, &false);
+    client.issue_certificate_with_expiry(&middle, &issuer, &owner, &metadata_uri, &3_000u64, &false);
+    client.issue_certificate(&never, &issuer, &owner, &metadata_uri, &None);
+    client.revoke_certificate(&revoked, &String::from_str(&env, "Revoked"));
+
+    assert_eq!(
+        client.get_certificates_expiring_before(&5_000u64, &0u32, &10u32),
+        vec![&env, soon.clone(), middle.clone()]
+    );
+    assert_eq!(
+        client.get_certificates_expiring_before(&10_000u64, &0u32, &10u32),
+        vec![&env, soon.clone(), middle.clone(), late.clone()]
+    );
+    assert_eq!(client.get_certificates_expiring_before(&2_000u64, &0u32, &10u32).len(), 0);
+
+    // Paging over the matches
+    assert_eq!(
+        client.get_certificates_expiring_before(&10_000u64, &0u32, &2u32),
+        vec![&env, soon, middle]
+    );
+    assert_eq!(
+        client.get_certificates_expiring_before(&10_000u64, &2u32, &2u32),
+        vec![&env, late]
+    );
+}
+
+#[test]
+fn test_rescind_accepted_transfer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-rescind");
+    let by_sender = String::from_str(&env, "transfer-rescind-sender");
+    let by_recipient = String::from_str(&env, "transfer-rescind-recipient");
+    let retry = String::from_str(&env, "transfer-rescind-retry");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmRescind"), &None);
+
+    // Pending transfers cannot be rescinded
+    client.initiate_transfer(&by_sender, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    let result = client.try_rescind_transfer(&by_sender, &owner);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidTransferStatus)));
+
+    // The sender backs out after acceptance
+    client.accept_transfer(&by_sender, &recipient);
+    let result = client.try_rescind_transfer(&by_sender, &stranger);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    client.rescind_transfer(&by_sender, &owner);
+    assert_eq!(client.get_transfer_status(&by_sender), TransferStatus::Cancelled);
+    assert_eq!(client.get_active_transfer(&cert_id), None);
+    assert_eq!(count_events(&env, symbol_short!("transfer_cancel"), &cert_id), 1);
+
+    // The recipient backs out after acceptance
+    client.initiate_transfer(&by_recipient, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&by_recipient, &recipient);
+    client.rescind_transfer(&by_recipient, &recipient);
+    assert_eq!(client.get_transfer_status(&by_recipient), TransferStatus::Cancelled);
+    assert_eq!(client.get_active_transfer(&cert_id), None);
+
+    // Ownership is untouched and the certificate can be transferred again
+    assert_eq!(client.get_certificate(&cert_id).owner, owner);
+    client.initiate_transfer(&retry, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    assert_eq!(client.get_active_transfer(&cert_id), Some(retry));
+}
+
+#[test]
+fn test_certificate_flag_bits() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmFlags");
+    let cert_id = String::from_str(&env, "cert-flags");
+    let auto_id = String::from_str(&env, "cert-flags-auto");
+
+    // Each flag owns a distinct bit and reads back through its accessor
+    let mut cert = create_test_certificate(&env, &client, "cert-flags-local", &issuer, &owner, create_version(&env, 1, 0, 0));
+    assert_eq!(cert.flags, 0);
+    cert.set_flag(FLAG_REVOKED, true);
+    assert_eq!(cert.flags, FLAG_REVOKED);
+    assert!(cert.is_revoked());
+    cert.set_flag(FLAG_REVOKED, false);
+    cert.set_flag(FLAG_FROZEN, true);
+    assert_eq!(cert.flags, FLAG_FROZEN);
+    assert!(cert.is_frozen());
+    cert.set_flag(FLAG_FROZEN, false);
+    cert.set_flag(FLAG_SUSPENDED, true);
+    assert_eq!(cert.flags, FLAG_SUSPENDED);
+    assert!(cert.is_suspended());
+    cert.set_flag(FLAG_SUSPENDED, false);
+    cert.set_flag(FLAG_RENOUNCED, true);
+    assert_eq!(cert.flags, FLAG_RENOUNCED);
+    assert!(cert.is_renounced());
+    cert.set_flag(FLAG_RENOUNCED, false);
+    cert.set_flag(FLAG_AUTO_REVOKE_ON_EXPIRY, true);
+    assert_eq!(cert.flags, FLAG_AUTO_REVOKE_ON_EXPIRY);
+    assert!(cert.auto_revoke_on_expiry());
+    assert!(!cert.is_revoked() && !cert.is_frozen() && !cert.is_suspended() && !cert.is_renounced());
+    let bits = [
+        FLAG_REVOKED,
+        FLAG_FROZEN,
+        FLAG_SUSPENDED,
+        FLAG_RENOUNCED,
+        FLAG_AUTO_REVOKE_ON_EXPIRY,
+    ];
+    let mut all = 0;
+    for bit in bits.iter() {
+        assert_eq!(bit.count_ones(), 1);
+        assert_eq!(all & bit, 0);
+        all |= bit;
+    }
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate_with_expiry(&auto_id, &issuer, &owner, &metadata_uri, &5_000u64, &true);
+
+    // Flags round-trip through storage and the boolean view
+    client.suspend_certificate(&cert_id, &String::from_str(&env, "Audit"));
+    let stored = client.get_certificate_opt(&cert_id).unwrap();
+    assert_eq!(stored.flags, FLAG_SUSPENDED);
+    let view = client.get_certificate(&cert_id);
+    assert!(view.suspended);
+    assert!(!view.revoked && !view.frozen && !view.renounced && !view.auto_revoke_on_expiry);
+
+    client.revoke_certificate(&auto_id, &String::from_str(&env, "Revoked"));
+    let stored = client.get_certificate_opt(&auto_id).unwrap();
+    assert_eq!(stored.flags, FLAG_REVOKED | FLAG_AUTO_REVOKE_ON_EXPIRY);
+    let view = client.get_certificate(&auto_id);
+    assert!(view.revoked && view.auto_revoke_on_expiry);
+    assert!(!view.frozen && !view.suspended && !view.renounced);
+    assert_eq!(view, CertificateView::from(stored));
+}
+
+#[test]
+fn test_transfer_approval_quorum() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let org = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let signer_1 = Address::generate(&env);
+    let signer_2 = Address::generate(&env);
+    let signer_3 = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-quorum");
+    let transfer_id = String::from_str(&env, "transfer-quorum");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &org, &String::from_str(&env, "ipfs://QmQuorum"), &None);
+
+    let approvers = vec![&env, signer_1.clone(), signer_2.clone(), signer_3.clone()];
+    let result = client.try_set_transfer_approvers(&cert_id, &approvers, &4u32);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    client.set_transfer_approvers(&cert_id, &approvers, &2u32);
+    assert_eq!(client.get_transfer_approvers(&cert_id).unwrap().threshold, 2);
+
+    client.initiate_transfer(&transfer_id, &cert_id, &org, &recipient, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&transfer_id, &recipient);
+
+    // No approvals yet
+    let result = client.try_complete_transfer(&transfer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::InsufficientApprovals)));
+
+    let result = client.try_approve_pending_transfer(&transfer_id, &outsider);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    assert_eq!(client.approve_pending_transfer(&transfer_id, &signer_1), 1);
+    let result = client.try_approve_pending_transfer(&transfer_id, &signer_1);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+
+    // One of two is not enough
+    let result = client.try_complete_transfer(&transfer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::InsufficientApprovals)));
+    assert_eq!(client.get_certificate(&cert_id).owner, org);
+
+    assert_eq!(client.approve_pending_transfer(&transfer_id, &signer_3), 2);
+    client.complete_transfer(&transfer_id, &recipient);
+    assert_eq!(client.get_certificate(&cert_id).owner, recipient);
+}
+
+#[test]
+fn test_approval_quorum_covers_direct_transfers_and_offers() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let org = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let signer_1 = Address::generate(&env);
+    let signer_2 = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-quorum-paths");
+    let offer_id = String::from_str(&env, "offer-quorum-paths");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.issue_certificate(&cert_id, &issuer, &org, &String::from_str(&env, "ipfs://QmQuorumPaths"), &None);
+    client.set_transfer_approvers(&cert_id, &vec![&env, signer_1.clone(), signer_2.clone()], &2u32);
+
+    // Direct transfers cannot gather approvals, so the quorum blocks them
+    let result = client.try_direct_transfer(&cert_id, &org, &recipient, &false);
+    assert_eq!(result, Err(Ok(CertificateError::InsufficientApprovals)));
+    assert_eq!(client.get_certificate(&cert_id).owner, org);
+
+    // Offers are claimable only once the quorum has signed off
+    client.create_open_offer(&offer_id, &cert_id, &org, &0i128, &None, &2_000u64);
+    assert_eq!(client.approve_pending_transfer(&offer_id, &signer_1), 1);
+    let result = client.try_claim_offer(&offer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::InsufficientApprovals)));
+    assert_eq!(client.get_certificate(&cert_id).owner, org);
+
+    assert_eq!(client.approve_pending_transfer(&offer_id, &signer_2), 2);
+    client.claim_offer(&offer_id, &recipient);
+    assert_eq!(client.get_certificate(&cert_id).owner, recipient);
+}
+
+#[test]
+fn test_max_transfers_auto_revokes() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-max-transfers");
+    let hop_1 = String::from_str(&env, "transfer-max-1");
+    let hop_2 = String::from_str(&env, "transfer-max-2");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &first, &String::from_str(&env, "ipfs://QmMaxTransfers"), &None);
+
+    let result = client.try_set_max_transfers(&cert_id, &Some(0));
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    client.set_max_transfers(&cert_id, &Some(2));
+
+    // The first transfer stays within the limit
+    client.initiate_transfer(&hop_1, &cert_id, &first, &second, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&hop_1, &second);
+    client.complete_transfer(&hop_1, &first);
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.transfer_count, 1);
+    assert!(!cert.revoked);
+
+    // The second transfer uses up the allowance and revokes the certificate
+    client.initiate_transfer(&hop_2, &cert_id, &second, &third, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&hop_2, &third);
+    client.complete_transfer(&hop_2, &second);
+    let cert = client.get_certificate(&cert_id);
+    assert_eq!(cert.owner, third);
+    assert_eq!(cert.transfer_count, 2);
+    assert!(cert.revoked);
+    assert_eq!(cert.revocation_reason, Some(String::from_str(&env, "TransferLimitReached")));
+    assert_eq!(cert.revoked_by, Some(issuer.clone()));
+
+    // The limit cannot be lowered below the transfers already made
+    let result = client.try_set_max_transfers(&cert_id, &Some(1));
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Direct transfers count against the same allowance
+    let direct_id = String::from_str(&env, "cert-max-transfers-direct");
+    client.issue_certificate(&direct_id, &issuer, &first, &String::from_str(&env, "ipfs://QmMaxDirect"), &None);
+    client.set_max_transfers(&direct_id, &Some(1));
+    client.direct_transfer(&direct_id, &first, &second, &false);
+    let cert = client.get_certificate(&direct_id);
+    assert_eq!(cert.transfer_count, 1);
+    assert!(cert.revoked);
+    assert_eq!(cert.revoked_by, Some(issuer));
+}
+
+#[test]
+fn test_can_accept() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmCanAccept");
+    let cert_id = String::from_str(&env, "cert-can-accept");
+    let expiring_cert = String::from_str(&env, "cert-can-accept-ttl");
+    let transfer_id = String::from_str(&env, "transfer-can-accept");
+    let expiring_transfer = String::from_str(&env, "transfer-can-accept-ttl");
+
+    env.mock_all_auths();
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&expiring_cert, &issuer, &owner, &metadata_uri, &None);
+
+    let result = client.try_can_accept(&transfer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::TransferNotFound)));
+
+    client.initiate_transfer(&transfer_id, &cert_id, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.can_accept(&transfer_id, &recipient);
+    assert_eq!(client.get_transfer_status(&transfer_id), TransferStatus::Pending);
+
+    let result = client.try_can_accept(&transfer_id, &stranger);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+
+    // A certificate that can no longer change hands blocks acceptance
+    client.suspend_certificate(&cert_id, &String::from_str(&env, "Under review"));
+    let result = client.try_can_accept(&transfer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateSuspended)));
+    let result = client.try_accept_transfer(&transfer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::CertificateSuspended)));
+    client.reinstate_from_suspension(&cert_id);
+
+    client.accept_transfer(&transfer_id, &recipient);
+    let result = client.try_can_accept(&transfer_id, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::TransferNotPending)));
+
+    // Lapsed transfers cannot be accepted
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let ttl = Some(TransferOptions { complete_by: None, ttl_seconds: 100, data: None, prepay_fee: false });
+    client.initiate_transfer_v2(&expiring_transfer, &expiring_cert, &owner, &recipient, &false, &0i128, &None, &false, &None, &ttl);
+    client.can_accept(&expiring_transfer, &recipient);
+    env.ledger().with_mut(|li| li.timestamp = 1_101);
+    let result = client.try_can_accept(&expiring_transfer, &recipient);
+    assert_eq!(result, Err(Ok(CertificateError::TransferExpired)));
+}
+
+#[test]
+fn test_certificate_id_format() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmIdFormat");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    // Without a format any ID is accepted
+    client.issue_certificate(&String::from_str(&env, "legacy-1"), &issuer, &owner, &metadata_uri, &None);
+
+    let result = client.try_set_certificate_id_format(&String::from_str(&env, "cert-"), &5);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    client.set_certificate_id_format(&String::from_str(&env, "cert-"), &12);
+    assert_eq!(client.get_certificate_id_format(), (String::from_str(&env, "cert-"), 12));
+
+    client.issue_certificate(&String::from_str(&env, "cert-1042"), &issuer, &owner, &metadata_uri, &None);
+
+    let result = client.try_issue_certificate(&String::from_str(&env, "license-1043"), &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    let result = client.try_issue_certificate(&String::from_str(&env, "cert-10440000"), &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // Clearing the format lifts both checks
+    client.set_certificate_id_format(&String::from_str(&env, ""), &0);
+    client.issue_certificate(&String::from_str(&env, "license-1043"), &issuer, &owner, &metadata_uri, &None);
+}
+
+#[test]
+fn test_active_and_revoked_counts() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmSupply");
+    let id1 = String::from_str(&env, "cert-supply-1");
+    let id2 = String::from_str(&env, "cert-supply-2");
+    let id3 = String::from_str(&env, "cert-supply-3");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(client.get_active_count(), 0);
+    assert_eq!(client.get_revoked_count(), 0);
+
+    client.issue_certificate(&id1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id2, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id3, &issuer, &owner, &metadata_uri, &None);
+    assert_eq!(client.get_active_count(), 3);
+    assert_eq!(client.get_revoked_count(), 0);
+
+    client.revoke_certificate(&id1, &String::from_str(&env, "Expired license"));
+    client.revoke_certificate(&id2, &String::from_str(&env, "Issued in error"));
+    assert_eq!(client.get_active_count(), 1);
+    assert_eq!(client.get_revoked_count(), 2);
+
+    // Reinstatement moves the certificate back to active
+    client.dispute_revocation(&id2, &owner, &String::from_str(&env, "Issued correctly"));
+    client.resolve_dispute(&id2, &admin, &false);
+    assert_eq!(client.get_active_count(), 2);
+    assert_eq!(client.get_revoked_count(), 1);
+
+    // Scheduled revocations count once applied, and only once
+    client.schedule_revocation(&id3, &1_000u64, &String::from_str(&env, "Course withdrawn"));
+    let result = client.try_apply_scheduled_revocation(&id3);
+    assert_eq!(result, Err(Ok(CertificateError::NotRevoked)));
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(client.get_revoked_count(), 1);
+    client.apply_scheduled_revocation(&id3);
+    assert_eq!(client.get_active_count(), 1);
+    assert_eq!(client.get_revoked_count(), 2);
+    assert_eq!(client.get_contract_stats().total_revoked, 2);
+    assert_eq!(client.get_revoked_certificates(&0, &10), vec![&env, id1.clone(), id3.clone()]);
+    let cert = client.get_certificate(&id3);
+    assert!(cert.revoked);
+    assert_eq!(cert.revoked_at, Some(1_000));
+    assert_eq!(cert.revocation_reason, Some(String::from_str(&env, "Course withdrawn")));
+
+    let result = client.try_apply_scheduled_revocation(&id3);
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+    let result = client.try_revoke_certificate(&id3, &String::from_str(&env, "Course withdrawn"));
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyRevoked)));
+    assert_eq!(client.get_revoked_count(), 2);
+}
+
+#[test]
+fn test_supply_counts_seed_from_indexes() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmSupplySeed");
+    let id1 = String::from_str(&env, "cert-supply-seed-1");
+    let id2 = String::from_str(&env, "cert-supply-seed-2");
+    let id3 = String::from_str(&env, "cert-supply-seed-3");
+
+    env.mock_all_auths();
+    client.issue_certificate(&id1, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&id2, &issuer, &owner, &metadata_uri, &None);
+    client.revoke_certificate(&id1, &String::from_str(&env, "Revoked"));
+
+    // Simulate a deployment that predates the counters
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Index(IndexKey::ActiveCount));
+        env.storage().instance().remove(&DataKey::Index(IndexKey::RevokedCount));
+    });
+
+    // The next writes seed the counters from the indexes and count on
+    client.issue_certificate(&id3, &issuer, &owner, &metadata_uri, &None);
+    client.revoke_certificate(&id2, &String::from_str(&env, "Revoked"));
+    assert_eq!(client.get_active_count(), 1);
+    assert_eq!(client.get_revoked_count(), 2);
+    assert_eq!(client.get_contract_stats().total_revoked, 2);
+}
+
+#[test]
+fn test_verifier_registry() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let kyc_verifier = Address::generate(&env);
+    let audit_verifier = Address::generate(&env);
+    let unregistered = Address::generate(&env);
+    let kyc_name = String::from_str(&env, "KYC Checks");
+    let audit_name = String::from_str(&env, "Audit Trail");
+    let cert_id = String::from_str(&env, "cert-verifier");
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(client.get_verifiers().len(), 0);
+
+    client.register_verifier(&kyc_verifier, &kyc_name);
+    client.register_verifier(&audit_verifier, &audit_name);
+    assert_eq!(
+        client.get_verifiers(),
+        vec![&env, (kyc_verifier.clone(), kyc_name.clone()), (audit_verifier.clone(), audit_name.clone())]
+    );
+
+    let result = client.try_register_verifier(&kyc_verifier, &String::from_str(&env, "KYC Again"));
+    assert_eq!(result, Err(Ok(CertificateError::AlreadyExists)));
+
+    // Certificates may only point at registered verifiers
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmVerifier"), &None);
+    assert_eq!(client.get_certificate(&cert_id).verifier, None);
+    let result = client.try_set_certificate_verifier(&cert_id, &Some(unregistered));
+    assert_eq!(result, Err(Ok(CertificateError::NotFound)));
+    client.set_certificate_verifier(&cert_id, &Some(audit_verifier.clone()));
+    assert_eq!(client.get_certificate(&cert_id).verifier, Some(audit_verifier));
+    client.set_certificate_verifier(&cert_id, &None);
+    assert_eq!(client.get_certificate(&cert_id).verifier, None);
+}
+
+#[test]
+fn test_open_offer_first_claim_wins() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmOffer");
+    let cert_id = String::from_str(&env, "cert-offer");
+    let stale_cert = String::from_str(&env, "cert-offer-stale");
+    let offer_id = String::from_str(&env, "offer-1");
+    let stale_offer = String::from_str(&env, "offer-stale");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.issue_certificate(&cert_id, &issuer, &owner, &metadata_uri, &None);
+    client.issue_certificate(&stale_cert, &issuer, &owner, &metadata_uri, &None);
+
+    let result = client.try_create_open_offer(&offer_id, &cert_id, &owner, &0i128, &None, &1_000);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+    let result = client.try_create_open_offer(&offer_id, &cert_id, &first, &0i128, &None, &2_000);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    client.create_open_offer(&offer_id, &cert_id, &owner, &0i128, &None, &2_000);
+    assert_eq!(client.get_open_offer(&offer_id).unwrap().status, TransferStatus::Pending);
+
+    // The offer holds the certificate's transfer slot
+    assert_eq!(client.get_active_transfer(&cert_id), Some(offer_id.clone()));
+    let direct_id = String::from_str(&env, "transfer-offer-clash");
+    let result = client.try_initiate_transfer(&direct_id, &cert_id, &owner, &second, &false, &0u64, &None, &false, &None);
+    assert_eq!(result, Err(Ok(CertificateError::TransferAlreadyActive)));
+
+    // The first claimer receives the certificate immediately
+    client.claim_offer(&offer_id, &first);
+    assert_eq!(client.get_certificate(&cert_id).owner, first);
+    assert_eq!(client.get_transfer_status(&offer_id), TransferStatus::Completed);
+    let offer = client.get_open_offer(&offer_id).unwrap();
+    assert_eq!(offer.status, TransferStatus::Completed);
+    assert_eq!(offer.claimed_by, Some(first.clone()));
+    assert_eq!(client.get_active_transfer(&cert_id), None);
+
+    // Later claimers find the offer spent
+    let result = client.try_claim_offer(&offer_id, &second);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidTransferStatus)));
+    assert_eq!(client.get_certificate(&cert_id).owner, first);
+
+    // Offers cannot be claimed once they lapse
+    client.create_open_offer(&stale_offer, &stale_cert, &owner, &0i128, &None, &1_500);
+    let result = client.try_cancel_open_offer(&stale_offer, &second);
+    assert_eq!(result, Err(Ok(CertificateError::Unauthorized)));
+    env.ledger().with_mut(|li| li.timestamp = 1_501);
+    let result = client.try_claim_offer(&stale_offer, &second);
+    assert_eq!(result, Err(Ok(CertificateError::TransferExpired)));
+    assert_eq!(client.get_certificate(&stale_cert).owner, owner);
+
+    // Anyone may clear a lapsed offer, which frees the certificate
+    client.cancel_open_offer(&stale_offer, &second);
+    assert_eq!(client.get_open_offer(&stale_offer).unwrap().status, TransferStatus::Cancelled);
+    assert_eq!(client.get_active_transfer(&stale_cert), None);
+    let retry = String::from_str(&env, "transfer-offer-retry");
+    client.initiate_transfer(&retry, &stale_cert, &owner, &second, &false, &0u64, &None, &false, &None);
+}
+
+#[test]
+fn test_open_offer_memo_on_fee() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let cert_id = String::from_str(&env, "cert-offer-memo");
+    let offer_id = String::from_str(&env, "offer-memo");
+    let memo = String::from_str(&env, "Invoice 42");
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.initialize(&admin);
+    client.set_require_memo_on_fee(&true);
+    client.issue_certificate(&cert_id, &issuer, &owner, &String::from_str(&env, "ipfs://QmOfferMemo"), &None);
+
+    let result = client.try_create_open_offer(&offer_id, &cert_id, &owner, &100i128, &None, &2_000);
+    assert_eq!(result, Err(Ok(CertificateError::InvalidData)));
+
+    // The memo travels with the offer into the completed transfer
+    client.create_open_offer(&offer_id, &cert_id, &owner, &100i128, &Some(memo.clone()), &2_000);
+    client.claim_offer(&offer_id, &claimer);
+    assert_eq!(client.get_certificate(&cert_id).owner, claimer);
+    assert_eq!(client.get_transfer(&offer_id).memo, Some(memo));
+}
+
+#[test]
+fn test_cleanup_expired_transfers_batch() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, CertificateContract);
+    let client = CertificateContractClient::new(&env, &contract_id);
+
+    let issuer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let metadata_uri = String::from_str(&env, "ipfs://QmBatchExpire");
+    let cert_a = String::from_str(&env, "cert-batch-expire-a");
+    let cert_b = String::from_str(&env, "cert-batch-expire-b");
+    let cert_c = String::from_str(&env, "cert-batch-expire-c");
+    let cert_d = String::from_str(&env, "cert-batch-expire-d");
+    let expired_a = String::from_str(&env, "transfer-batch-expired-a");
+    let expired_b = String::from_str(&env, "transfer-batch-expired-b");
+    let active = String::from_str(&env, "transfer-batch-active");
+    let accepted = String::from_str(&env, "transfer-batch-accepted");
+    let unknown = String::from_str(&env, "transfer-batch-unknown");
+
+    env.mock_all_auths();
+    for cert_id in [&cert_a, &cert_b, &cert_c, &cert_d] {
+        client.issue_certificate(cert_id, &issuer, &owner, &metadata_uri, &None);
+    }
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let short_ttl = Some(TransferOptions { complete_by: None, ttl_seconds: 100, data: None, prepay_fee: false });
+    let long_ttl = Some(TransferOptions { complete_by: None, ttl_seconds: 500, data: None, prepay_fee: false });
+    client.initiate_transfer_v2(&expired_a, &cert_a, &owner, &recipient, &false, &0i128, &None, &false, &None, &short_ttl);
+    client.initiate_transfer_v2(&expired_b, &cert_b, &owner, &recipient, &false, &0i128, &None, &false, &None, &short_ttl);
+    client.initiate_transfer_v2(&active, &cert_c, &owner, &recipient, &false, &0i128, &None, &false, &None, &long_ttl);
+    client.initiate_transfer(&accepted, &cert_d, &owner, &recipient, &false, &0u64, &None, &false, &None);
+    client.accept_transfer(&accepted, &recipient);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_200);
+    let ids = vec![&env, expired_a.clone(), active.clone(), accepted.clone(), unknown, expired_b.clone()];
+    assert_eq!(client.cleanup_expired_transfers(&ids), 2);
+
+    assert_eq!(client.get_transfer_status(&expired_a), TransferStatus::Cancelled);
+    assert_eq!(client.get_transfer_status(&expired_b), TransferStatus::Cancelled);
+    assert_eq!(client.get_transfer_status(&active), TransferStatus::Pending);
+    assert_eq!(client.get_transfer_status(&accepted), TransferStatus::Accepted);
+    assert_eq!(client.get_pending_transfer_count(&recipient), 1);
+
+    // A second pass finds nothing left to clean
+    assert_eq!(client.cleanup_expired_transfers(&ids), 0);
+}